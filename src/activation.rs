@@ -0,0 +1,128 @@
+use super::*;
+
+use std::sync::RwLock;
+
+pub type ActivationFunction = fn(f32) -> f32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activation {
+    #[default]
+    #[serde(alias = "Sigmoid")]
+    Sigmoid,
+    #[serde(alias = "SteepenedSigmoid")]
+    SteepenedSigmoid,
+    #[serde(alias = "Tanh")]
+    Tanh,
+    #[serde(alias = "Relu")]
+    Relu,
+    #[serde(alias = "LeakyRelu")]
+    LeakyRelu,
+    #[serde(alias = "Identity")]
+    Identity,
+    #[serde(alias = "Gaussian")]
+    Gaussian,
+    #[serde(alias = "Sine")]
+    Sine,
+    #[serde(alias = "Step")]
+    Step,
+    #[serde(alias = "Abs")]
+    Abs,
+    #[serde(alias = "Custom")]
+    Custom(CustomActivation),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomActivation(usize);
+
+static CUSTOM_ACTIVATIONS: RwLock<Vec<(String, ActivationFunction)>> = RwLock::new(Vec::new());
+
+impl Activation {
+    pub fn register(name: &str, function: ActivationFunction) -> Self {
+        let mut custom = CUSTOM_ACTIVATIONS.write().unwrap();
+        let index = match custom
+            .iter()
+            .position(|(custom_name, _)| custom_name == name)
+        {
+            Some(index) => {
+                custom[index].1 = function;
+                index
+            }
+            None => {
+                custom.push((name.to_owned(), function));
+                custom.len() - 1
+            }
+        };
+        Activation::Custom(CustomActivation(index))
+    }
+
+    pub fn custom(name: &str) -> Option<Self> {
+        CUSTOM_ACTIVATIONS
+            .read()
+            .unwrap()
+            .iter()
+            .position(|(custom_name, _)| custom_name == name)
+            .map(|index| Activation::Custom(CustomActivation(index)))
+    }
+
+    pub fn function(self) -> ActivationFunction {
+        match self {
+            Activation::Sigmoid => |value| 1.0 / (1.0 + (-value).exp()),
+            Activation::SteepenedSigmoid => |value| 1.0 / (1.0 + (-4.9 * value).exp()),
+            Activation::Tanh => f32::tanh,
+            Activation::Relu => |value| value.max(0.0),
+            Activation::LeakyRelu => |value| if value > 0.0 { value } else { 0.01 * value },
+            Activation::Identity => |value| value,
+            Activation::Gaussian => |value| (-value * value).exp(),
+            Activation::Sine => f32::sin,
+            Activation::Step => |value| if value > 0.0 { 1.0 } else { 0.0 },
+            Activation::Abs => f32::abs,
+            Activation::Custom(CustomActivation(index)) => {
+                CUSTOM_ACTIVATIONS.read().unwrap()[index].1
+            }
+        }
+    }
+
+    pub fn apply(self, value: f32) -> f32 {
+        self.function()(value)
+    }
+}
+
+impl std::fmt::Display for Activation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Activation::Sigmoid => write!(f, "sigmoid"),
+            Activation::SteepenedSigmoid => write!(f, "steepened_sigmoid"),
+            Activation::Tanh => write!(f, "tanh"),
+            Activation::Relu => write!(f, "relu"),
+            Activation::LeakyRelu => write!(f, "leaky_relu"),
+            Activation::Identity => write!(f, "identity"),
+            Activation::Gaussian => write!(f, "gaussian"),
+            Activation::Sine => write!(f, "sine"),
+            Activation::Step => write!(f, "step"),
+            Activation::Abs => write!(f, "abs"),
+            Activation::Custom(CustomActivation(index)) => {
+                write!(f, "{}", CUSTOM_ACTIVATIONS.read().unwrap()[*index].0)
+            }
+        }
+    }
+}
+
+impl Serialize for CustomActivation {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&CUSTOM_ACTIVATIONS.read().unwrap()[self.0].0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomActivation {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        match Activation::custom(&name) {
+            Some(Activation::Custom(custom)) => Ok(custom),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown custom activation `{}`",
+                name
+            ))),
+        }
+    }
+}
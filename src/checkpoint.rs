@@ -0,0 +1,286 @@
+use super::*;
+
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+use std::path::Path;
+
+const CHECKPOINT_VERSION: u32 = 1;
+const GENOME_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct Versioned<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct VersionHeader {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct VersionedOwned<T> {
+    data: T,
+}
+
+pub(crate) fn save_versioned<T: Serialize>(
+    path: impl AsRef<Path>,
+    version: u32,
+    data: &T,
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer(&mut writer, &Versioned { version, data })?;
+    writer.flush()
+}
+
+pub(crate) fn load_versioned<T: DeserializeOwned>(
+    path: impl AsRef<Path>,
+    version: u32,
+) -> std::io::Result<T> {
+    let contents = std::fs::read_to_string(path)?;
+    let header: VersionHeader = serde_json::from_str(&contents)?;
+    if header.version != version {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "unsupported format version {}, expected {}",
+                header.version, version
+            ),
+        ));
+    }
+    let versioned: VersionedOwned<T> = serde_json::from_str(&contents)?;
+    Ok(versioned.data)
+}
+
+impl Neat {
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        save_versioned(path, CHECKPOINT_VERSION, self)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        load_versioned(path, CHECKPOINT_VERSION)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeExport {
+    innovation_number: usize,
+    x: f32,
+    y: f32,
+    activation: Activation,
+    bias: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConnectionExport {
+    innovation_number: usize,
+    from: usize,
+    to: usize,
+    weight: f32,
+    enabled: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GenomeExport {
+    input_nodes: Vec<NodeExport>,
+    hidden_nodes: Vec<NodeExport>,
+    output_nodes: Vec<NodeExport>,
+    connections: Vec<ConnectionExport>,
+}
+
+impl From<&NodeGene> for NodeExport {
+    fn from(node: &NodeGene) -> Self {
+        Self {
+            innovation_number: node.gene.innovation_number,
+            x: node.x,
+            y: node.y,
+            activation: node.activation,
+            bias: node.bias,
+        }
+    }
+}
+
+impl From<&NodeExport> for NodeGene {
+    fn from(node: &NodeExport) -> Self {
+        Self {
+            gene: Gene {
+                innovation_number: node.innovation_number,
+            },
+            x: node.x,
+            y: node.y,
+            activation: node.activation,
+            bias: node.bias,
+        }
+    }
+}
+
+impl From<&Genome> for GenomeExport {
+    fn from(genome: &Genome) -> Self {
+        Self {
+            input_nodes: genome.input_nodes.iter().map(NodeExport::from).collect(),
+            hidden_nodes: genome.hidden_nodes.iter().map(NodeExport::from).collect(),
+            output_nodes: genome.output_nodes.iter().map(NodeExport::from).collect(),
+            connections: genome
+                .connections
+                .iter()
+                .map(|connection| ConnectionExport {
+                    innovation_number: connection.innovation_number,
+                    from: connection.node_from.gene.innovation_number,
+                    to: connection.node_to.gene.innovation_number,
+                    weight: connection.weight,
+                    enabled: connection.enabled,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl GenomeExport {
+    fn into_genome(self) -> Result<Genome, NeatError> {
+        let mut genome = Genome {
+            input_nodes: self.input_nodes.iter().map(NodeGene::from).collect(),
+            hidden_nodes: self.hidden_nodes.iter().map(NodeGene::from).collect(),
+            output_nodes: self.output_nodes.iter().map(NodeGene::from).collect(),
+            connections: Vec::with_capacity(self.connections.len()),
+        };
+        for connection in &self.connections {
+            let node = |innovation_number| {
+                genome
+                    .node(Gene { innovation_number })
+                    .copied()
+                    .ok_or_else(|| {
+                        NeatError::InvalidGenome(format!(
+                            "connection {} references missing node {}",
+                            connection.innovation_number, innovation_number
+                        ))
+                    })
+            };
+            let gene = ConnectionGene::new(
+                Gene {
+                    innovation_number: connection.innovation_number,
+                },
+                node(connection.from)?,
+                node(connection.to)?,
+                connection.weight,
+                connection.enabled,
+            );
+            genome.connections.push(gene);
+        }
+        // The export does not record whether the genome came from a recurrent population, so
+        // only the structural invariants are checked here.
+        genome.validate(true)?;
+        Ok(genome)
+    }
+}
+
+impl Genome {
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        save_versioned(path, GENOME_VERSION, &GenomeExport::from(self))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        load_versioned::<GenomeExport>(path, GENOME_VERSION)?
+            .into_genome()
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fitness(genome: &Genome) -> f32 {
+        genome
+            .calculate(vec![0.5, -1.0])
+            .iter()
+            .map(|output| 1.0 - (output - 0.25).abs())
+            .sum()
+    }
+
+    #[test]
+    fn loaded_checkpoint_continues_identically() {
+        let config = NeatConfig::builder(2, 1).seed(11).build().unwrap();
+        let mut neat = Neat::new(config);
+        for _ in 0..20 {
+            neat.evaluate(fitness);
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("neat-checkpoint-{}.json", std::process::id()));
+        neat.save(&path).unwrap();
+        let mut loaded = Neat::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        for _ in 0..20 {
+            neat.evaluate(fitness);
+            loaded.evaluate(fitness);
+        }
+        assert_eq!(neat.generation, loaded.generation);
+        assert_eq!(
+            format!("{:?}", neat.clients),
+            format!("{:?}", loaded.clients)
+        );
+        assert_eq!(
+            format!("{:?}", neat.species),
+            format!("{:?}", loaded.species)
+        );
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("neat-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn genome_export_round_trips() {
+        let config = NeatConfig::builder(2, 1)
+            .seed(3)
+            .probability_mutate_node(0.5)
+            .build()
+            .unwrap();
+        let mut neat = Neat::new(config);
+        for _ in 0..30 {
+            neat.evaluate(fitness);
+        }
+        let genome = &neat
+            .clients
+            .values()
+            .max_by_key(|client| client.genome.hidden_nodes.len())
+            .unwrap()
+            .genome;
+        assert!(!genome.hidden_nodes.is_empty());
+
+        let path = temp_path("genome");
+        genome.save(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let loaded = Genome::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(!contents.contains("replace_gene"));
+        assert_eq!(
+            serde_json::to_string(&GenomeExport::from(genome)).unwrap(),
+            serde_json::to_string(&GenomeExport::from(&loaded)).unwrap()
+        );
+        assert_eq!(
+            genome.calculate(vec![0.3, 0.7]),
+            loaded.calculate(vec![0.3, 0.7])
+        );
+    }
+
+    #[test]
+    fn corrupt_genome_fails_to_load() {
+        let path = temp_path("corrupt-genome");
+        let mut export = GenomeExport::from(&Genome::empty(&NeatConfig::default()));
+        export.connections.push(ConnectionExport {
+            innovation_number: 5,
+            from: 0,
+            to: 42,
+            weight: 1.0,
+            enabled: true,
+        });
+        save_versioned(&path, GENOME_VERSION, &export).unwrap();
+        let error = Genome::load(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
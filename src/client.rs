@@ -1,10 +1,11 @@
 use super::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Client {
     pub id: Id,
     pub genome: Genome,
     pub score: f32,
+    pub adjusted_score: f32,
 }
 
 impl Client {
@@ -13,9 +14,23 @@ impl Client {
             id,
             genome,
             score: 0.0,
+            adjusted_score: 0.0,
         }
     }
     pub fn calculate(&self, input: Vec<f32>) -> Vec<f32> {
         self.genome.calculate(input)
     }
+    pub fn try_calculate(&self, input: Vec<f32>) -> Result<Vec<f32>, NeatError> {
+        self.genome.try_calculate(input)
+    }
+    pub fn ranking_score(&self) -> f32 {
+        if self.score.is_nan() {
+            f32::NEG_INFINITY
+        } else {
+            self.score
+        }
+    }
+    pub fn calculate_batch(&self, inputs: &[f32]) -> Vec<f32> {
+        self.genome.calculate_batch(inputs)
+    }
 }
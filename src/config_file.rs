@@ -0,0 +1,234 @@
+use super::*;
+
+#[cfg(any(feature = "json", feature = "toml", feature = "ron"))]
+use std::path::Path;
+
+impl NeatConfig {
+    pub const PRESETS: [&'static str; 3] = ["xor", "classic-neat", "large-population"];
+
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "xor" => Some(Self {
+                input_size: 2,
+                output_size: 1,
+                target_species_count: 5,
+                ..Self::default()
+            }),
+            "classic-neat" => Some(Self {
+                probability_mutate_link: 0.05,
+                probability_mutate_node: 0.03,
+                ..Self::default()
+            }),
+            "large-population" => Some(Self {
+                max_clients: 1000,
+                target_species_count: 30,
+                probability_mutate_link: 0.3,
+                probability_mutate_node: 0.03,
+                protected_species: 5,
+                hall_of_fame_size: 25,
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(feature = "json", feature = "toml", feature = "ron"))]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, NeatError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|error| NeatError::ConfigFile(format!("{}: {}", path.display(), error)))?;
+        let config = match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "toml")]
+            Some("toml") => Self::from_toml_str(&contents),
+            #[cfg(feature = "json")]
+            Some("json") => Self::from_json_str(&contents),
+            #[cfg(feature = "ron")]
+            Some("ron") => Self::from_ron_str(&contents),
+            _ => Err(NeatError::ConfigFile(
+                "unsupported config file extension".to_owned(),
+            )),
+        };
+        config.map_err(|error| match error {
+            NeatError::ConfigFile(message) => {
+                NeatError::ConfigFile(format!("{}: {}", path.display(), message))
+            }
+            error => error,
+        })
+    }
+
+    #[cfg(feature = "json")]
+    pub fn from_json_str(contents: &str) -> Result<Self, NeatError> {
+        serde_json::from_str::<PartialConfig>(contents)
+            .map_err(|error| NeatError::ConfigFile(error.to_string()))?
+            .merge()
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(contents: &str) -> Result<Self, NeatError> {
+        toml::from_str::<PartialConfig>(contents)
+            .map_err(|error| NeatError::ConfigFile(error.to_string()))?
+            .merge()
+    }
+
+    #[cfg(feature = "ron")]
+    pub fn from_ron_str(contents: &str) -> Result<Self, NeatError> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str::<PartialConfig>(contents)
+            .map_err(|error| NeatError::ConfigFile(error.to_string()))?
+            .merge()
+    }
+}
+
+#[cfg(any(feature = "json", feature = "toml", feature = "ron"))]
+macro_rules! partial_config {
+    ($($field:ident: $ty:ty,)*) => {
+        /// Every field of `NeatConfig` made optional, plus the name of the preset to start from.
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct PartialConfig {
+            preset: Option<String>,
+            $($field: Option<$ty>,)*
+        }
+
+        impl PartialConfig {
+            /// Fields missing from the file are taken from the preset, or from
+            /// `NeatConfig::default()` when there is none.
+            fn merge(self) -> Result<NeatConfig, NeatError> {
+                let mut config = match self.preset {
+                    Some(name) => NeatConfig::preset(&name).ok_or_else(|| {
+                        NeatError::ConfigFile(format!(
+                            "unknown preset `{}`, expected one of {:?}",
+                            name,
+                            NeatConfig::PRESETS
+                        ))
+                    })?,
+                    None => NeatConfig::default(),
+                };
+                $(
+                    if let Some($field) = self.$field {
+                        config.$field = $field;
+                    }
+                )*
+                config.validate()?;
+                Ok(config)
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "json", feature = "toml", feature = "ron"))]
+partial_config! {
+    input_size: usize,
+    output_size: usize,
+    max_clients: usize,
+    disjoint: f32,
+    excess: f32,
+    weight_diff: f32,
+    bias_diff: f32,
+    cp: f32,
+    cp_change_rate: f32,
+    target_species_count: usize,
+    probability_mutate_link: f32,
+    probability_mutate_node: f32,
+    probability_mutate_weight_shift: f32,
+    probability_mutate_weight_random: f32,
+    probability_mutate_link_toggle: f32,
+    weight_shift_strength: f32,
+    weight_random_strength: f32,
+    probability_mutate_bias_shift: f32,
+    probability_mutate_bias_random: f32,
+    bias_shift_strength: f32,
+    bias_random_strength: f32,
+    clients_mutation_rate: f32,
+    survivors_percentage: f32,
+    stagnation_limit: usize,
+    protected_species: usize,
+    elitism: usize,
+    elitism_min_species_size: usize,
+    hall_of_fame_size: usize,
+    seed: Option<u64>,
+    recurrent: bool,
+    hidden_activation: Activation,
+    output_activation: Activation,
+    activation_options: Vec<Activation>,
+    probability_mutate_activation: f32,
+    probability_inherit_disabled: f32,
+}
+
+#[cfg(all(test, any(feature = "json", feature = "toml", feature = "ron")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_fields_override_the_preset() {
+        let config =
+            NeatConfig::from_json_str(r#"{"preset": "xor", "max_clients": 50, "seed": 7}"#)
+                .unwrap();
+        assert_eq!(config.input_size, 2);
+        assert_eq!(config.target_species_count, 5);
+        assert_eq!(config.max_clients, 50);
+        assert_eq!(config.seed, Some(7));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn every_config_field_can_be_loaded() {
+        for config in [
+            NeatConfig::default(),
+            NeatConfig::builder(3, 2)
+                .seed(5)
+                .recurrent(true)
+                .activation_options(vec![Activation::Relu])
+                .probability_mutate_activation(0.2)
+                .build()
+                .unwrap(),
+        ] {
+            let json = serde_json::to_string(&config).unwrap();
+            let loaded = NeatConfig::from_json_str(&json).unwrap();
+            assert_eq!(format!("{:?}", loaded), format!("{:?}", config));
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn unknown_fields_and_presets_are_rejected() {
+        assert!(matches!(
+            NeatConfig::from_json_str(r#"{"bogus": 1}"#),
+            Err(NeatError::ConfigFile(_))
+        ));
+        assert!(matches!(
+            NeatConfig::from_json_str(r#"{"preset": "nope"}"#),
+            Err(NeatError::ConfigFile(_))
+        ));
+        assert!(matches!(
+            NeatConfig::from_json_str(r#"{"max_clients": 0}"#),
+            Err(NeatError::InvalidConfig(_))
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_partial_config() {
+        let config = NeatConfig::from_toml_str("cp = 2.0\nhidden_activation = \"tanh\"\n").unwrap();
+        assert_eq!(config.cp, 2.0);
+        assert_eq!(config.hidden_activation, Activation::Tanh);
+        assert_eq!(config.max_clients, NeatConfig::default().max_clients);
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn ron_enum_variants_keep_their_names() {
+        let config = NeatConfig::from_ron_str(
+            "(preset: \"xor\", hidden_activation: Tanh, activation_options: [Relu, gaussian])",
+        )
+        .unwrap();
+        assert_eq!(config.input_size, 2);
+        assert_eq!(config.hidden_activation, Activation::Tanh);
+        assert_eq!(
+            config.activation_options,
+            vec![Activation::Relu, Activation::Gaussian]
+        );
+    }
+}
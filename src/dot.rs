@@ -0,0 +1,148 @@
+use super::*;
+
+use std::fmt::Write;
+
+impl Genome {
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph genome {{").unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        writeln!(dot, "    node [style=filled];").unwrap();
+
+        for node in &self.input_nodes {
+            Self::write_dot_node(&mut dot, node, "box", "lightblue");
+        }
+        for node in &self.hidden_nodes {
+            Self::write_dot_node(&mut dot, node, "circle", "white");
+        }
+        for node in &self.output_nodes {
+            Self::write_dot_node(&mut dot, node, "doublecircle", "lightsalmon");
+        }
+
+        let mut nodes = self.nodes();
+        nodes.sort_by(|a, b| a.x.total_cmp(&b.x));
+        for rank in nodes.chunk_by(|a, b| a.x == b.x) {
+            write!(dot, "    {{ rank=same;").unwrap();
+            for node in rank {
+                write!(dot, " n{};", node.gene.innovation_number).unwrap();
+            }
+            writeln!(dot, " }}").unwrap();
+        }
+
+        let max_weight = self
+            .connections
+            .iter()
+            .map(|connection| connection.weight.abs())
+            .fold(0.0, f32::max)
+            .max(f32::EPSILON);
+        for connection in &self.connections {
+            let strength = (connection.weight.abs() / max_weight).min(1.0);
+            let (red, blue) = if connection.weight >= 0.0 {
+                (0, 255)
+            } else {
+                (255, 0)
+            };
+            writeln!(
+                dot,
+                "    n{} -> n{} [label=\"{:.3}\", color=\"#{:02x}00{:02x}{:02x}\", penwidth={:.2}, style={}];",
+                connection.node_from.gene.innovation_number,
+                connection.node_to.gene.innovation_number,
+                connection.weight,
+                red,
+                blue,
+                (64.0 + 191.0 * strength) as u8,
+                0.5 + 3.5 * strength,
+                if connection.enabled { "solid" } else { "dashed" },
+            )
+            .unwrap();
+        }
+
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    fn write_dot_node(dot: &mut String, node: &NodeGene, shape: &str, color: &str) {
+        writeln!(
+            dot,
+            "    n{} [label=\"{}\\n{}\", shape={}, fillcolor={}];",
+            node.gene.innovation_number,
+            node.gene.innovation_number,
+            escape_dot(&node.activation.to_string()),
+            shape,
+            color,
+        )
+        .unwrap();
+    }
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome() -> Genome {
+        let mut genome = Genome::empty(&NeatConfig::builder(2, 1).build().unwrap());
+        let hidden = NodeGene::new(3, 0.5, 0.5, Activation::Relu);
+        genome.hidden_nodes.insert(hidden);
+        let (input1, input2, output) = (
+            genome.input_nodes[0],
+            genome.input_nodes[1],
+            genome.output_nodes[0],
+        );
+        for (innovation_number, from, to, weight, enabled) in [
+            (4, input1, hidden, 1.0, true),
+            (5, hidden, output, -0.5, false),
+            (6, input2, output, 0.25, true),
+        ] {
+            genome.connections.push(ConnectionGene::new(
+                Gene { innovation_number },
+                from,
+                to,
+                weight,
+                enabled,
+            ));
+        }
+        genome
+    }
+
+    fn edge(dot: &str, from: usize, to: usize) -> &str {
+        let prefix = format!("    n{} -> n{} [", from, to);
+        dot.lines().find(|line| line.starts_with(&prefix)).unwrap()
+    }
+
+    #[test]
+    fn nodes_are_ranked_by_x() {
+        let dot = genome().to_dot();
+        assert!(dot.contains("{ rank=same; n0; n1; }"));
+        assert!(dot.contains("{ rank=same; n3; }"));
+        assert!(dot.contains("{ rank=same; n2; }"));
+        let ranks: Vec<&str> = dot
+            .lines()
+            .filter(|line| line.contains("rank=same"))
+            .collect();
+        assert_eq!(ranks.len(), 3);
+    }
+
+    #[test]
+    fn edges_show_sign_and_enabled_state() {
+        let dot = genome().to_dot();
+        let positive = edge(&dot, 0, 3);
+        assert!(positive.contains("color=\"#0000ffff\""));
+        assert!(positive.contains("style=solid"));
+        let negative = edge(&dot, 3, 2);
+        assert!(negative.contains("color=\"#ff0000"));
+        assert!(negative.contains("style=dashed"));
+        assert!(edge(&dot, 1, 2).contains("color=\"#0000ff"));
+    }
+
+    #[test]
+    fn activation_names_are_escaped() {
+        let mut genome = Genome::empty(&NeatConfig::default());
+        genome.output_nodes[0].activation = Activation::register("dot\\\"quoted", f32::abs);
+        let dot = genome.to_dot();
+        assert!(dot.contains("n1 [label=\"1\\ndot\\\\\\\"quoted\", "));
+    }
+}
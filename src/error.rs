@@ -0,0 +1,30 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NeatError {
+    InputSize { expected: usize, actual: usize },
+    InvalidFitness { client: Id, score: f32 },
+    EmptyPopulation,
+    InvalidConfig(String),
+    ConfigFile(String),
+    InvalidGenome(String),
+}
+
+impl std::fmt::Display for NeatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NeatError::InputSize { expected, actual } => {
+                write!(f, "expected {} inputs, got {}", expected, actual)
+            }
+            NeatError::InvalidFitness { client, score } => {
+                write!(f, "client {} has invalid fitness {}", client, score)
+            }
+            NeatError::EmptyPopulation => write!(f, "population is empty"),
+            NeatError::InvalidConfig(message) => write!(f, "invalid config: {}", message),
+            NeatError::ConfigFile(message) => write!(f, "failed to load config: {}", message),
+            NeatError::InvalidGenome(message) => write!(f, "invalid genome: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for NeatError {}
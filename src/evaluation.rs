@@ -0,0 +1,103 @@
+use super::*;
+
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StopCondition {
+    FitnessThreshold(f32),
+    MaxGenerations(usize),
+    TimeBudget(Duration),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StopReason {
+    FitnessThreshold(f32),
+    MaxGenerations(usize),
+    TimeBudget(Duration),
+    Reporter,
+}
+
+impl From<StopCondition> for StopReason {
+    fn from(condition: StopCondition) -> Self {
+        match condition {
+            StopCondition::FitnessThreshold(threshold) => StopReason::FitnessThreshold(threshold),
+            StopCondition::MaxGenerations(generations) => StopReason::MaxGenerations(generations),
+            StopCondition::TimeBudget(budget) => StopReason::TimeBudget(budget),
+        }
+    }
+}
+
+impl Neat {
+    pub fn evaluate(&mut self, mut fitness: impl FnMut(&Genome) -> f32) -> GenerationStats {
+        for client in self.clients.values_mut() {
+            client.score = fitness(&client.genome);
+        }
+        self.evolve()
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn evaluate_par(&mut self, fitness: impl Fn(&Genome) -> f32 + Sync) -> GenerationStats {
+        self.clients
+            .par_iter_mut()
+            .for_each(|(_, client)| client.score = fitness(&client.genome));
+        self.evolve()
+    }
+
+    pub fn run(
+        &mut self,
+        mut fitness: impl FnMut(&Genome) -> f32,
+        until: &[StopCondition],
+    ) -> StopReason {
+        self.run_with(until, |neat| neat.evaluate(&mut fitness))
+    }
+
+    #[cfg(feature = "rayon")]
+    pub fn run_par(
+        &mut self,
+        fitness: impl Fn(&Genome) -> f32 + Sync,
+        until: &[StopCondition],
+    ) -> StopReason {
+        self.run_with(until, |neat| neat.evaluate_par(&fitness))
+    }
+
+    fn run_with(
+        &mut self,
+        until: &[StopCondition],
+        mut evaluate: impl FnMut(&mut Self) -> GenerationStats,
+    ) -> StopReason {
+        assert!(!until.is_empty(), "at least one stop condition is required");
+        let start = Instant::now();
+        loop {
+            let stats = evaluate(self);
+            let reporter_stop = self
+                .reporters
+                .iter_mut()
+                .any(|reporter| reporter.should_stop(&stats));
+            if let Some(reason) = self.check_stop_conditions(until, start, reporter_stop) {
+                return reason;
+            }
+        }
+    }
+
+    fn check_stop_conditions(
+        &self,
+        until: &[StopCondition],
+        start: Instant,
+        reporter_stop: bool,
+    ) -> Option<StopReason> {
+        if reporter_stop {
+            return Some(StopReason::Reporter);
+        }
+        until
+            .iter()
+            .find(|condition| match **condition {
+                StopCondition::FitnessThreshold(threshold) => self
+                    .best
+                    .as_ref()
+                    .is_some_and(|best| best.score >= threshold),
+                StopCondition::MaxGenerations(generations) => self.generation >= generations,
+                StopCondition::TimeBudget(budget) => start.elapsed() >= budget,
+            })
+            .map(|&condition| condition.into())
+    }
+}
@@ -1,19 +1,37 @@
-#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq)]
+use super::*;
+
+#[derive(Debug, Hash, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct Gene {
     pub innovation_number: usize,
 }
 
-impl Gene {
-    pub fn new() -> Self {
-        static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneGenerator {
+    next_innovation_number: usize,
+}
+
+impl GeneGenerator {
+    pub fn new(first_innovation_number: usize) -> Self {
         Self {
-            innovation_number: NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            next_innovation_number: first_innovation_number,
         }
     }
+
+    pub fn next_innovation_number(&self) -> usize {
+        self.next_innovation_number
+    }
+
+    pub fn gen(&mut self) -> Gene {
+        let gene = Gene {
+            innovation_number: self.next_innovation_number,
+        };
+        self.next_innovation_number += 1;
+        gene
+    }
 }
 
-impl Default for Gene {
+impl Default for GeneGenerator {
     fn default() -> Self {
-        Self::new()
+        Self::new(0)
     }
 }
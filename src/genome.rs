@@ -1,39 +1,38 @@
 use super::*;
 
-use rand::Rng;
-
-use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Genome {
-    pub input_nodes: HashSet<NodeGene>,
-    pub hidden_nodes: HashSet<NodeGene>,
-    pub output_nodes: HashSet<NodeGene>,
+    pub input_nodes: Vec<NodeGene>,
+    pub hidden_nodes: BTreeSet<NodeGene>,
+    pub output_nodes: Vec<NodeGene>,
     pub connections: Vec<ConnectionGene>,
 }
 
 impl Genome {
     pub fn empty(neat_config: &NeatConfig) -> Self {
-        let mut input_nodes = HashSet::new();
-        let mut output_nodes = HashSet::new();
+        let mut input_nodes = Vec::with_capacity(neat_config.input_size);
+        let mut output_nodes = Vec::with_capacity(neat_config.output_size);
         for i in 0..neat_config.input_size {
-            input_nodes.insert(NodeGene::new(
+            input_nodes.push(NodeGene::new(
                 i,
                 0.0,
                 (i as f32 + 1.0) / (neat_config.input_size as f32 + 1.0),
+                Activation::Identity,
             ));
         }
         for i in 0..neat_config.output_size {
-            output_nodes.insert(NodeGene::new(
+            output_nodes.push(NodeGene::new(
                 neat_config.input_size + i,
                 1.0,
                 (i as f32 + 1.0) / (neat_config.output_size as f32 + 1.0),
+                neat_config.output_activation,
             ));
         }
         Self {
             input_nodes,
-            hidden_nodes: HashSet::new(),
+            hidden_nodes: BTreeSet::new(),
             output_nodes,
             connections: Vec::new(),
         }
@@ -49,44 +48,156 @@ impl Genome {
         )
     }
 
+    pub fn node(&self, gene: Gene) -> Option<&NodeGene> {
+        self.input_nodes
+            .iter()
+            .chain(&self.output_nodes)
+            .find(|node| node.gene == gene)
+            .or_else(|| self.hidden_nodes.get(&gene))
+    }
+
     pub fn calculate(&self, input: Vec<f32>) -> Vec<f32> {
         assert_eq!(input.len(), self.input_nodes.len());
 
-        let mut nodes_output = HashMap::with_capacity(
-            self.input_nodes.len() + self.hidden_nodes.len() + self.output_nodes.len(),
-        );
+        let mut nodes_output = BTreeMap::new();
 
-        for input_node in &self.input_nodes {
-            nodes_output.insert(input_node.gene, input[input_node.gene.innovation_number]);
+        for (input_node, value) in self.input_nodes.iter().zip(input) {
+            nodes_output.insert(input_node.gene, value);
         }
 
         let mut output = Vec::with_capacity(self.output_nodes.len());
         for output_node in &self.output_nodes {
-            let value = output_node.calculate(&self.connections, &mut nodes_output);
+            let value = output_node.calculate(self, &mut nodes_output);
             output.push(value);
         }
 
         output
     }
 
-    pub fn calculate_debug(&self, input: Vec<f32>) -> HashMap<Gene, f32> {
+    pub fn try_calculate(&self, input: Vec<f32>) -> Result<Vec<f32>, NeatError> {
+        if input.len() != self.input_nodes.len() {
+            return Err(NeatError::InputSize {
+                expected: self.input_nodes.len(),
+                actual: input.len(),
+            });
+        }
+        Ok(self.calculate(input))
+    }
+
+    pub fn calculate_debug(&self, input: Vec<f32>) -> BTreeMap<Gene, f32> {
         assert_eq!(input.len(), self.input_nodes.len());
 
-        let mut output = HashMap::with_capacity(
-            self.input_nodes.len() + self.hidden_nodes.len() + self.output_nodes.len(),
-        );
+        let mut output = BTreeMap::new();
 
-        for input_node in &self.input_nodes {
-            output.insert(input_node.gene, input[input_node.gene.innovation_number]);
+        for (input_node, value) in self.input_nodes.iter().zip(input) {
+            output.insert(input_node.gene, value);
         }
 
         for output_node in &self.output_nodes {
-            output_node.calculate(&self.connections, &mut output);
+            output_node.calculate(self, &mut output);
         }
 
         output
     }
 
+    pub fn validate(&self, recurrent: bool) -> Result<(), NeatError> {
+        fn invalid(message: String) -> Result<(), NeatError> {
+            Err(NeatError::InvalidGenome(message))
+        }
+
+        let mut nodes = BTreeSet::new();
+        for node in self
+            .input_nodes
+            .iter()
+            .chain(&self.hidden_nodes)
+            .chain(&self.output_nodes)
+        {
+            if !nodes.insert(node.gene) {
+                return invalid(format!(
+                    "node {} appears more than once",
+                    node.gene.innovation_number
+                ));
+            }
+        }
+
+        if let Some(pair) = self
+            .connections
+            .windows(2)
+            .find(|pair| pair[0].innovation_number >= pair[1].innovation_number)
+        {
+            return invalid(format!(
+                "connection {} is out of order after connection {}",
+                pair[1].innovation_number, pair[0].innovation_number
+            ));
+        }
+
+        let mut edges: BTreeMap<Gene, Vec<Gene>> = BTreeMap::new();
+        for connection in &self.connections {
+            for node in [connection.node_from, connection.node_to].iter() {
+                if !nodes.contains(&node.gene) {
+                    return invalid(format!(
+                        "connection {} references missing node {}",
+                        connection.innovation_number, node.gene.innovation_number
+                    ));
+                }
+            }
+            if self.input_nodes.contains(&connection.node_to) {
+                return invalid(format!(
+                    "connection {} leads into input node {}",
+                    connection.innovation_number, connection.node_to.gene.innovation_number
+                ));
+            }
+            edges
+                .entry(connection.node_from.gene)
+                .or_default()
+                .push(connection.node_to.gene);
+        }
+
+        if !recurrent {
+            if let Some(gene) = Self::find_cycle(&edges) {
+                return invalid(format!(
+                    "feed-forward genome has a cycle through node {}",
+                    gene.innovation_number
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn find_cycle(edges: &BTreeMap<Gene, Vec<Gene>>) -> Option<Gene> {
+        let mut finished = BTreeSet::new();
+        for &start in edges.keys() {
+            if finished.contains(&start) {
+                continue;
+            }
+            let mut path = BTreeSet::new();
+            path.insert(start);
+            let mut stack = vec![(start, 0)];
+            while let Some((node, index)) = stack.last_mut() {
+                let node = *node;
+                match edges.get(&node).and_then(|targets| targets.get(*index)) {
+                    Some(&target) => {
+                        *index += 1;
+                        if path.contains(&target) {
+                            return Some(target);
+                        }
+                        if !finished.contains(&target) {
+                            path.insert(target);
+                            stack.push((target, 0));
+                        }
+                    }
+                    None => {
+                        stack.pop();
+                        path.remove(&node);
+                        finished.insert(node);
+                    }
+                }
+            }
+        }
+        None
+    }
+
     pub fn distance(&self, other: &Self, neat_config: &NeatConfig) -> f32 {
         let highest_gene1 = if let Some(gene) = self.connections.last() {
             gene.innovation_number
@@ -136,19 +247,49 @@ impl Genome {
         weight_diff /= similar.max(1) as f32;
         let excess = genome1.connections.len() - index1;
 
-        let n = genome1.connections.len().max(genome2.connections.len()) as f32;
+        let mut bias_diff = 0.0;
+        let mut similar_nodes = 0;
+        for node in self.hidden_nodes.iter().chain(&self.output_nodes) {
+            if let Some(other_node) = other.node(node.gene) {
+                similar_nodes += 1;
+                bias_diff += (node.bias - other_node.bias).abs();
+            }
+        }
+        bias_diff /= similar_nodes.max(1) as f32;
+
+        let n = genome1
+            .connections
+            .len()
+            .max(genome2.connections.len())
+            .max(1) as f32;
 
         (neat_config.disjoint * disjoint as f32
             + neat_config.excess * excess as f32
-            + neat_config.weight_diff * weight_diff)
+            + neat_config.weight_diff * weight_diff
+            + neat_config.bias_diff * bias_diff)
             / n
     }
 
-    pub fn cross_over(genome1: &Genome, genome2: &Genome, neat_config: &NeatConfig) -> Genome {
+    pub fn cross_over(
+        genome1: &Genome,
+        score1: f32,
+        genome2: &Genome,
+        score2: f32,
+        neat_config: &NeatConfig,
+        random: &mut impl Rng,
+    ) -> Genome {
+        let equal = score1 == score2;
+        let (genome1, genome2) = if score2 > score1 {
+            (genome2, genome1)
+        } else {
+            (genome1, genome2)
+        };
+
         let mut genome = Genome::empty(neat_config);
 
         let mut index1 = 0;
         let mut index2 = 0;
+        let mut parents = Vec::new();
 
         while index1 < genome1.connections.len() && index2 < genome2.connections.len() {
             let con1 = genome1.connections.get(index1).unwrap();
@@ -156,19 +297,30 @@ impl Genome {
 
             match con1.innovation_number.cmp(&con2.innovation_number) {
                 std::cmp::Ordering::Equal => {
-                    if rand::thread_rng().gen_bool(0.5) {
-                        genome.connections.push(*con1);
+                    let (mut connection, parent) = if random.gen_bool(0.5) {
+                        (*con1, genome1)
                     } else {
-                        genome.connections.push(*con2);
+                        (*con2, genome2)
+                    };
+                    if !con1.enabled || !con2.enabled {
+                        connection.enabled =
+                            !random.gen_bool(neat_config.probability_inherit_disabled as f64);
                     }
+                    genome.connections.push(connection);
+                    parents.push(parent);
                     index1 += 1;
                     index2 += 1;
                 }
                 std::cmp::Ordering::Greater => {
+                    if equal {
+                        genome.connections.push(*con2);
+                        parents.push(genome2);
+                    }
                     index2 += 1;
                 }
                 std::cmp::Ordering::Less => {
                     genome.connections.push(*con1);
+                    parents.push(genome1);
                     index1 += 1;
                 }
             }
@@ -177,14 +329,38 @@ impl Genome {
         while index1 < genome1.connections.len() {
             let con1 = genome1.connections.get(index1).unwrap();
             genome.connections.push(*con1);
+            parents.push(genome1);
             index1 += 1;
         }
 
-        for connection in &genome.connections {
-            genome.hidden_nodes.insert(connection.node_from);
-            genome.hidden_nodes.insert(connection.node_to);
+        while equal && index2 < genome2.connections.len() {
+            let con2 = genome2.connections.get(index2).unwrap();
+            genome.connections.push(*con2);
+            parents.push(genome2);
+            index2 += 1;
         }
 
+        let mut sources = BTreeMap::new();
+        for (connection, parent) in genome.connections.iter().zip(parents) {
+            for gene in [connection.node_from.gene, connection.node_to.gene].iter() {
+                sources.entry(*gene).or_insert(parent);
+            }
+        }
+
+        for (gene, parent) in &sources {
+            if let Some(node) = parent.hidden_nodes.get(gene) {
+                genome.hidden_nodes.insert(*node);
+            }
+        }
+        genome.output_nodes = genome1
+            .output_nodes
+            .iter()
+            .map(|node| match sources.get(&node.gene) {
+                Some(parent) => *parent.node(node.gene).unwrap(),
+                None => *node,
+            })
+            .collect();
+
         genome
     }
 
@@ -192,22 +368,46 @@ impl Genome {
         &mut self,
         neat_config: &NeatConfig,
         connection_genes: &mut HashSet<ConnectionGene>,
+        gene_gen: &mut GeneGenerator,
+        random: &mut impl Rng,
+    ) {
+        self.mutate_structure(neat_config, connection_genes, gene_gen, random);
+        self.mutate_parameters(neat_config, random);
+    }
+
+    pub fn mutate_structure(
+        &mut self,
+        neat_config: &NeatConfig,
+        connection_genes: &mut HashSet<ConnectionGene>,
+        gene_gen: &mut GeneGenerator,
+        random: &mut impl Rng,
     ) {
-        let mut random = rand::thread_rng();
         if random.gen::<f32>() <= neat_config.probability_mutate_link {
-            self.mutate_link(neat_config, connection_genes);
+            self.mutate_link(neat_config, connection_genes, gene_gen, random);
         }
         if random.gen::<f32>() <= neat_config.probability_mutate_node {
-            self.mutate_node(connection_genes);
+            self.mutate_node(neat_config, connection_genes, gene_gen, random);
         }
+    }
+
+    pub fn mutate_parameters(&mut self, neat_config: &NeatConfig, random: &mut impl Rng) {
         if random.gen::<f32>() <= neat_config.probability_mutate_weight_shift {
-            self.mutate_weight_shift(neat_config);
+            self.mutate_weight_shift(neat_config, random);
         }
         if random.gen::<f32>() <= neat_config.probability_mutate_weight_random {
-            self.mutate_weight_random(neat_config);
+            self.mutate_weight_random(neat_config, random);
         }
         if random.gen::<f32>() <= neat_config.probability_mutate_link_toggle {
-            self.mutate_link_toggle();
+            self.mutate_link_toggle(random);
+        }
+        if random.gen::<f32>() <= neat_config.probability_mutate_bias_shift {
+            self.mutate_bias_shift(neat_config, random);
+        }
+        if random.gen::<f32>() <= neat_config.probability_mutate_bias_random {
+            self.mutate_bias_random(neat_config, random);
+        }
+        if random.gen::<f32>() <= neat_config.probability_mutate_activation {
+            self.mutate_activation(neat_config, random);
         }
     }
 
@@ -215,28 +415,33 @@ impl Genome {
         &mut self,
         neat_config: &NeatConfig,
         connection_genes: &mut HashSet<ConnectionGene>,
+        gene_gen: &mut GeneGenerator,
+        random: &mut impl Rng,
     ) {
-        let mut random = rand::thread_rng();
-        let nodes: Vec<_> = self
+        let mut nodes: Vec<_> = self
             .input_nodes
             .iter()
             .chain(&self.hidden_nodes)
             .chain(&self.output_nodes)
             .collect();
+        nodes.sort_by_key(|node| node.gene.innovation_number);
         let nodes_len = self.input_nodes.len() + self.hidden_nodes.len() + self.output_nodes.len();
         if nodes_len >= 2 {
             for _ in 0..10 {
-                let node1 = **nodes.choose(&mut random).unwrap();
-                let node2 = **nodes.choose(&mut random).unwrap();
+                let node1 = **nodes.choose(random).unwrap();
+                let node2 = **nodes.choose(random).unwrap();
 
-                if node1.x == node2.x {
-                    continue;
-                }
-
-                let (node_from, node_to) = if node1.x < node2.x {
+                let (node_from, node_to) = if neat_config.recurrent {
+                    if self.input_nodes.contains(&node2) {
+                        continue;
+                    }
                     (node1, node2)
-                } else {
+                } else if node1.x < node2.x {
+                    (node1, node2)
+                } else if node1.x > node2.x {
                     (node2, node1)
+                } else {
+                    continue;
                 };
 
                 if self.connections.iter().any(|connection| {
@@ -247,6 +452,7 @@ impl Genome {
 
                 let connection = Neat::get_connection_gene(
                     connection_genes,
+                    gene_gen,
                     node_from,
                     node_to,
                     random.gen_range(-1.0, 1.0) * neat_config.weight_shift_strength,
@@ -254,23 +460,34 @@ impl Genome {
                 );
                 self.connections.push(connection);
                 self.connections
-                    .sort_by(|con1, con2| con1.innovation_number.cmp(&con2.innovation_number))
+                    .sort_by_key(|connection| connection.innovation_number)
             }
         }
     }
 
-    fn mutate_node(&mut self, connection_genes: &mut HashSet<ConnectionGene>) {
+    fn mutate_node(
+        &mut self,
+        neat_config: &NeatConfig,
+        connection_genes: &mut HashSet<ConnectionGene>,
+        gene_gen: &mut GeneGenerator,
+        random: &mut impl Rng,
+    ) {
         if self.connections.is_empty() {
             return;
         }
-        let mut random = rand::thread_rng();
         let connection = &self.connections[random.gen_range(0, self.connections.len())];
         let node_from = connection.node_from;
         let node_to = connection.node_to;
         let mut connection = *connection_genes
             .iter()
             .find(|connection| connection.node_from == node_from && connection.node_to == node_to)
-            .unwrap();
+            .unwrap_or(connection);
+        if connection
+            .replace_gene
+            .is_some_and(|gene| self.hidden_nodes.contains(&gene))
+        {
+            return;
+        }
         let middle_x = (node_from.x + node_to.x) / 2.0;
         let middle_y = (node_from.y + node_to.y) / 2.0;
         let middle = match connection.replace_gene {
@@ -278,11 +495,15 @@ impl Genome {
                 gene,
                 x: middle_x,
                 y: middle_y,
+                activation: neat_config.hidden_activation,
+                bias: 0.0,
             },
             None => NodeGene {
-                gene: Gene::new(),
+                gene: gene_gen.gen(),
                 x: middle_x,
                 y: middle_y,
+                activation: neat_config.hidden_activation,
+                bias: 0.0,
             },
         };
         connection.replace_gene = Some(middle.gene);
@@ -292,40 +513,237 @@ impl Genome {
 
         connection_genes.replace(connection);
 
-        let connection1 = Neat::get_connection_gene(connection_genes, node_from, middle, 1.0, true);
+        let connection1 =
+            Neat::get_connection_gene(connection_genes, gene_gen, node_from, middle, 1.0, true);
         let connection2 =
-            Neat::get_connection_gene(connection_genes, middle, node_to, weight, enabled);
+            Neat::get_connection_gene(connection_genes, gene_gen, middle, node_to, weight, enabled);
         self.hidden_nodes.insert(middle);
         self.connections.push(connection1);
         self.connections.push(connection2);
         self.connections
-            .sort_by(|con1, con2| con1.innovation_number.cmp(&con2.innovation_number))
+            .sort_by_key(|connection| connection.innovation_number)
     }
 
-    fn mutate_weight_shift(&mut self, neat_config: &NeatConfig) {
+    fn mutate_weight_shift(&mut self, neat_config: &NeatConfig, random: &mut impl Rng) {
         let count = self.connections.len();
         if count >= 1 {
-            let mut random = rand::thread_rng();
             let connection = &mut self.connections[random.gen_range(0, count)];
             connection.weight += random.gen_range(-1.0, 1.0) * neat_config.weight_shift_strength;
         }
     }
 
-    fn mutate_weight_random(&mut self, neat_config: &NeatConfig) {
+    fn mutate_weight_random(&mut self, neat_config: &NeatConfig, random: &mut impl Rng) {
         let count = self.connections.len();
         if count >= 1 {
-            let mut random = rand::thread_rng();
             let connection = &mut self.connections[random.gen_range(0, count)];
             connection.weight = random.gen_range(-1.0, 1.0) * neat_config.weight_random_strength;
         }
     }
 
-    fn mutate_link_toggle(&mut self) {
+    fn mutate_link_toggle(&mut self, random: &mut impl Rng) {
         let count = self.connections.len();
         if count >= 1 {
-            let mut random = rand::thread_rng();
             let connection = &mut self.connections[random.gen_range(0, count)];
             connection.enabled = !connection.enabled;
         }
     }
+
+    fn mutate_bias_shift(&mut self, neat_config: &NeatConfig, random: &mut impl Rng) {
+        let shift = random.gen_range(-1.0, 1.0) * neat_config.bias_shift_strength;
+        self.mutate_random_node(random, |node| node.bias += shift);
+    }
+
+    fn mutate_bias_random(&mut self, neat_config: &NeatConfig, random: &mut impl Rng) {
+        let bias = random.gen_range(-1.0, 1.0) * neat_config.bias_random_strength;
+        self.mutate_random_node(random, |node| node.bias = bias);
+    }
+
+    fn mutate_activation(&mut self, neat_config: &NeatConfig, random: &mut impl Rng) {
+        if let Some(&activation) = neat_config.activation_options.choose(random) {
+            self.mutate_random_node(random, |node| node.activation = activation);
+        }
+    }
+
+    fn mutate_random_node(&mut self, random: &mut impl Rng, mutation: impl FnOnce(&mut NodeGene)) {
+        let count = self.hidden_nodes.len() + self.output_nodes.len();
+        if count >= 1 {
+            let index = random.gen_range(0, count);
+            if index < self.output_nodes.len() {
+                mutation(&mut self.output_nodes[index]);
+            } else {
+                let mut node = *self
+                    .hidden_nodes
+                    .iter()
+                    .nth(index - self.output_nodes.len())
+                    .unwrap();
+                mutation(&mut node);
+                self.hidden_nodes.replace(node);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(probability_inherit_disabled: f32) -> NeatConfig {
+        NeatConfig::builder(2, 1)
+            .probability_inherit_disabled(probability_inherit_disabled)
+            .build()
+            .unwrap()
+    }
+
+    fn hidden(innovation_number: usize, bias: f32, activation: Activation) -> NodeGene {
+        NodeGene {
+            bias,
+            ..NodeGene::new(innovation_number, 0.5, 0.5, activation)
+        }
+    }
+
+    fn parent(hidden_nodes: &[NodeGene], links: &[(usize, usize, usize, f32, bool)]) -> Genome {
+        let mut genome = Genome::empty(&config(0.75));
+        genome.hidden_nodes.extend(hidden_nodes.iter().copied());
+        for &(innovation_number, from, to, weight, enabled) in links {
+            let from = *genome
+                .node(Gene {
+                    innovation_number: from,
+                })
+                .unwrap();
+            let to = *genome
+                .node(Gene {
+                    innovation_number: to,
+                })
+                .unwrap();
+            genome.connections.push(ConnectionGene::new(
+                Gene { innovation_number },
+                from,
+                to,
+                weight,
+                enabled,
+            ));
+        }
+        genome.validate(false).unwrap();
+        genome
+    }
+
+    fn parents() -> (Genome, Genome) {
+        let genome1 = parent(
+            &[hidden(3, 0.5, Activation::Relu)],
+            &[
+                (5, 0, 2, 1.0, true),
+                (6, 0, 3, 1.0, true),
+                (7, 3, 2, 1.0, true),
+            ],
+        );
+        let genome2 = parent(
+            &[hidden(4, -0.5, Activation::Gaussian)],
+            &[
+                (5, 0, 2, 2.0, true),
+                (8, 1, 4, 2.0, true),
+                (9, 4, 2, 2.0, true),
+            ],
+        );
+        (genome1, genome2)
+    }
+
+    fn innovations(genome: &Genome) -> Vec<usize> {
+        genome
+            .connections
+            .iter()
+            .map(|connection| connection.innovation_number)
+            .collect()
+    }
+
+    #[test]
+    fn try_calculate_rejects_wrong_input_size() {
+        let genome = Genome::empty(&config(0.75));
+        assert_eq!(
+            genome.try_calculate(vec![1.0]),
+            Err(NeatError::InputSize {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(genome.try_calculate(vec![1.0, 0.0]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn equal_scores_inherit_genes_from_both_parents() {
+        let (genome1, genome2) = parents();
+        let mut random = NeatRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let child =
+                Genome::cross_over(&genome1, 1.0, &genome2, 1.0, &config(0.75), &mut random);
+            child.validate(false).unwrap();
+            assert_eq!(innovations(&child), vec![5, 6, 7, 8, 9]);
+            assert_eq!(child.hidden_nodes.len(), 2);
+        }
+    }
+
+    #[test]
+    fn unequal_scores_keep_only_the_fitter_parents_genes() {
+        let (genome1, genome2) = parents();
+        let config = config(0.75);
+        let mut random = NeatRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let child = Genome::cross_over(&genome1, 2.0, &genome2, 1.0, &config, &mut random);
+            assert_eq!(innovations(&child), vec![5, 6, 7]);
+            let child = Genome::cross_over(&genome2, 1.0, &genome1, 2.0, &config, &mut random);
+            assert_eq!(innovations(&child), vec![5, 6, 7]);
+            let child = Genome::cross_over(&genome1, 1.0, &genome2, 2.0, &config, &mut random);
+            assert_eq!(innovations(&child), vec![5, 8, 9]);
+            child.validate(false).unwrap();
+        }
+    }
+
+    #[test]
+    fn disabled_matching_genes_follow_the_configured_probability() {
+        let (genome1, mut genome2) = parents();
+        genome2.connections[0].enabled = false;
+        let mut random = NeatRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let child = Genome::cross_over(&genome1, 2.0, &genome2, 1.0, &config(1.0), &mut random);
+            assert!(!child.connections[0].enabled);
+            let child = Genome::cross_over(&genome1, 2.0, &genome2, 1.0, &config(0.0), &mut random);
+            assert!(child.connections[0].enabled);
+            assert!(child.connections[1..]
+                .iter()
+                .all(|connection| connection.enabled));
+        }
+    }
+
+    #[test]
+    fn hidden_nodes_come_from_the_parent_supplying_the_connection() {
+        let genome1 = parent(
+            &[hidden(3, 0.5, Activation::Relu)],
+            &[(6, 0, 3, 1.0, true), (7, 3, 2, 1.0, true)],
+        );
+        let genome2 = parent(
+            &[hidden(3, -0.5, Activation::Gaussian)],
+            &[(6, 0, 3, 2.0, true), (7, 3, 2, 2.0, true)],
+        );
+        let mut random = NeatRng::seed_from_u64(0);
+        let mut sources = BTreeSet::new();
+        for _ in 0..50 {
+            let child =
+                Genome::cross_over(&genome1, 2.0, &genome2, 1.0, &config(0.75), &mut random);
+            let source = if child.connections[0].weight == 1.0 {
+                &genome1
+            } else {
+                &genome2
+            };
+            let node = child
+                .hidden_nodes
+                .get(&Gene {
+                    innovation_number: 3,
+                })
+                .unwrap();
+            let expected = source.hidden_nodes.get(&node.gene).unwrap();
+            assert_eq!(node.bias, expected.bias);
+            assert_eq!(node.activation, expected.activation);
+            sources.insert(node.activation == Activation::Relu);
+        }
+        assert_eq!(sources.len(), 2);
+    }
 }
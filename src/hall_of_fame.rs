@@ -0,0 +1,184 @@
+use super::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HallOfFameEntry {
+    pub genome: Genome,
+    pub score: f32,
+    pub generation: usize,
+    pub species: Option<Id>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HallOfFame {
+    capacity: usize,
+    entries: Vec<HallOfFameEntry>,
+}
+
+impl HallOfFame {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn entries(&self) -> &[HallOfFameEntry] {
+        &self.entries
+    }
+
+    pub fn best(&self) -> Option<&HallOfFameEntry> {
+        self.entries.first()
+    }
+
+    pub fn qualifies(&self, score: f32) -> bool {
+        score.is_finite()
+            && self.capacity > 0
+            && (self.entries.len() < self.capacity
+                || self.entries.last().is_some_and(|worst| score > worst.score))
+    }
+
+    pub fn insert(&mut self, entry: HallOfFameEntry) -> bool {
+        if !self.qualifies(entry.score)
+            || self
+                .entries
+                .iter()
+                .any(|other| other.score == entry.score && identical(&other.genome, &entry.genome))
+        {
+            return false;
+        }
+        let index = self
+            .entries
+            .iter()
+            .position(|other| entry.score > other.score)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+        self.entries.truncate(self.capacity);
+        true
+    }
+}
+
+fn identical(genome1: &Genome, genome2: &Genome) -> bool {
+    let node = |node: &NodeGene| (node.gene, node.x, node.y, node.activation, node.bias);
+    let connection = |connection: &ConnectionGene| {
+        (
+            connection.gene,
+            connection.node_from.gene,
+            connection.node_to.gene,
+            connection.weight,
+            connection.enabled,
+        )
+    };
+    genome1
+        .nodes()
+        .iter()
+        .map(node)
+        .eq(genome2.nodes().iter().map(node))
+        && genome1
+            .connections
+            .iter()
+            .map(connection)
+            .eq(genome2.connections.iter().map(connection))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome(weight: f32) -> Genome {
+        let mut genome = Genome::empty(&NeatConfig::default());
+        let (input, output) = (genome.input_nodes[0], genome.output_nodes[0]);
+        genome.connections.push(ConnectionGene::new(
+            Gene {
+                innovation_number: 2,
+            },
+            input,
+            output,
+            weight,
+            true,
+        ));
+        genome
+    }
+
+    fn entry(score: f32, weight: f32) -> HallOfFameEntry {
+        HallOfFameEntry {
+            genome: genome(weight),
+            score,
+            generation: 0,
+            species: None,
+        }
+    }
+
+    fn scores(hall_of_fame: &HallOfFame) -> Vec<f32> {
+        hall_of_fame
+            .entries()
+            .iter()
+            .map(|entry| entry.score)
+            .collect()
+    }
+
+    #[test]
+    fn entries_are_sorted_and_truncated() {
+        let mut hall_of_fame = HallOfFame::new(3);
+        for (index, &score) in [2.0, 5.0, 1.0, 4.0, 3.0].iter().enumerate() {
+            hall_of_fame.insert(entry(score, index as f32));
+        }
+        assert_eq!(scores(&hall_of_fame), vec![5.0, 4.0, 3.0]);
+        assert_eq!(hall_of_fame.best().unwrap().score, 5.0);
+        assert!(!hall_of_fame.insert(entry(3.0, 9.0)));
+        assert!(hall_of_fame.insert(entry(3.5, 9.0)));
+        assert_eq!(scores(&hall_of_fame), vec![5.0, 4.0, 3.5]);
+    }
+
+    #[test]
+    fn non_finite_scores_are_rejected() {
+        let mut hall_of_fame = HallOfFame::new(3);
+        assert!(!hall_of_fame.insert(entry(f32::NAN, 0.0)));
+        assert!(!hall_of_fame.insert(entry(f32::INFINITY, 0.0)));
+        assert!(!hall_of_fame.insert(entry(f32::NEG_INFINITY, 0.0)));
+        assert!(hall_of_fame.entries().is_empty());
+    }
+
+    #[test]
+    fn only_identical_genomes_are_duplicates() {
+        let mut hall_of_fame = HallOfFame::new(5);
+        assert!(hall_of_fame.insert(entry(1.0, 0.5)));
+        assert!(!hall_of_fame.insert(entry(1.0, 0.5)));
+        assert!(hall_of_fame.insert(entry(1.0, -0.5)));
+
+        let mut disabled = entry(1.0, 0.5);
+        disabled.genome.connections[0].enabled = false;
+        assert!(hall_of_fame.insert(disabled));
+
+        let mut biased = entry(1.0, 0.5);
+        biased.genome.output_nodes[0].bias = 0.25;
+        assert!(hall_of_fame.insert(biased));
+        assert_eq!(hall_of_fame.entries().len(), 4);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn survives_checkpoints() {
+        let config = NeatConfig::builder(2, 1).seed(1).build().unwrap();
+        let mut neat = Neat::new(config);
+        for _ in 0..5 {
+            neat.evaluate(|genome| genome.calculate(vec![0.5, 1.0])[0]);
+        }
+        assert!(!neat.hall_of_fame.entries().is_empty());
+
+        let path =
+            std::env::temp_dir().join(format!("neat-hall-of-fame-{}.json", std::process::id()));
+        neat.save(&path).unwrap();
+        let loaded = Neat::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.hall_of_fame.capacity(), neat.hall_of_fame.capacity());
+        assert_eq!(
+            format!("{:?}", loaded.hall_of_fame.entries()),
+            format!("{:?}", neat.hall_of_fame.entries())
+        );
+    }
+}
@@ -20,6 +20,12 @@ impl IdGenerator {
     }
 }
 
+impl std::fmt::Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl Default for IdGenerator {
     fn default() -> Self {
         Self::new()
@@ -1,22 +1,48 @@
 use rand::prelude::SliceRandom;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::{Arc, Mutex};
 
+mod activation;
+#[cfg(feature = "json")]
+mod checkpoint;
 mod client;
+mod config_file;
 mod connection_gene;
+mod dot;
+mod error;
+mod evaluation;
 mod gene;
 mod genome;
+mod hall_of_fame;
 mod id_generator;
 mod neat;
+mod neat_config;
+mod network;
 mod node_gene;
 mod species;
+mod stats;
+mod stats_exporter;
+mod svg;
 
+pub use activation::*;
 use client::*;
 use connection_gene::*;
+pub use error::*;
+pub use evaluation::*;
+pub use gene::Gene;
 use gene::*;
-use genome::*;
+pub use genome::*;
+pub use hall_of_fame::*;
 pub use id_generator::Id;
 use id_generator::*;
 pub use neat::*;
+pub use neat_config::*;
+pub use network::*;
 use node_gene::*;
 use species::*;
+pub use stats::*;
+pub use stats_exporter::*;
@@ -1,96 +1,245 @@
 use super::*;
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct NeatConfig {
-    pub input_size: usize,
-    pub output_size: usize,
-    pub max_clients: usize,
-    pub disjoint: f32,
-    pub excess: f32,
-    pub weight_diff: f32,
-    pub cp: f32,
-    pub cp_change_rate: f32,
-    pub target_species_count: usize,
-    pub probability_mutate_link: f32,
-    pub probability_mutate_node: f32,
-    pub probability_mutate_weight_shift: f32,
-    pub probability_mutate_weight_random: f32,
-    pub probability_mutate_link_toggle: f32,
-    pub weight_shift_strength: f32,
-    pub weight_random_strength: f32,
-    pub clients_mutation_rate: f32,
-    pub survivors_percentage: f32,
-}
+pub type NeatRng = rand_pcg::Pcg64;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Neat {
     pub id_gen: IdGenerator,
+    pub species_id_gen: IdGenerator,
+    pub gene_gen: GeneGenerator,
     pub config: NeatConfig,
-    pub clients: HashMap<Id, Client>,
+    pub clients: BTreeMap<Id, Client>,
     pub connection_genes: HashSet<ConnectionGene>,
     pub species: Vec<Species>,
+    pub random: NeatRng,
+    pub generation: usize,
+    pub best: Option<Client>,
+    pub hall_of_fame: HallOfFame,
+    #[serde(skip)]
+    pub reporters: Vec<Box<dyn Reporter>>,
 }
 
 impl Neat {
     pub fn new(neat_config: NeatConfig) -> Self {
-        for _ in 0..(neat_config.input_size + neat_config.output_size) {
-            Gene::new();
-        }
+        Self::try_new(neat_config).unwrap_or_else(|error| panic!("{}", error))
+    }
 
+    pub fn try_new(neat_config: NeatConfig) -> Result<Self, NeatError> {
+        neat_config.validate()?;
+
+        let random = match neat_config.seed {
+            Some(seed) => NeatRng::seed_from_u64(seed),
+            None => NeatRng::from_entropy(),
+        };
+
+        let hall_of_fame = HallOfFame::new(neat_config.hall_of_fame_size);
         let clients_count = neat_config.max_clients;
         let mut neat = Self {
             id_gen: IdGenerator::new(),
+            species_id_gen: IdGenerator::new(),
+            gene_gen: GeneGenerator::new(neat_config.input_size + neat_config.output_size),
             config: neat_config,
-            clients: HashMap::with_capacity(clients_count),
+            clients: BTreeMap::new(),
             connection_genes: HashSet::new(),
             species: Vec::new(),
+            random,
+            generation: 0,
+            best: None,
+            hall_of_fame,
+            reporters: Vec::new(),
         };
         for _ in 0..clients_count {
             let client = Client::new(neat.id_gen.gen(), Genome::empty(&neat.config));
             neat.clients.insert(client.id, client);
         }
-        neat
+        Ok(neat)
+    }
+
+    /// Returns a shared handle, so the reporter can still be inspected while `Neat` drives it.
+    pub fn add_reporter<R: Reporter + 'static>(&mut self, reporter: R) -> Arc<Mutex<R>> {
+        let reporter = Arc::new(Mutex::new(reporter));
+        self.reporters.push(Box::new(Arc::clone(&reporter)));
+        reporter
     }
 
-    pub fn evolve(&mut self) {
+    pub fn evolve(&mut self) -> GenerationStats {
+        let innovations = self.gene_gen.next_innovation_number();
+        self.record_best();
         self.gen_species();
+        let mut stats = GenerationStats::new(self);
+        self.record_hall_of_fame();
+        self.remove_stagnant_species();
+        let elites = self.elites();
         self.kill();
         self.remove_extinct_species();
-        self.reproduce();
-        self.mutate();
+        self.reproduce(&elites);
+        self.mutate(&elites);
+        if cfg!(debug_assertions) {
+            self.assert_valid_genomes();
+        }
+        stats.new_innovations = self.gene_gen.next_innovation_number() - innovations;
+        for reporter in &mut self.reporters {
+            reporter.report(&stats);
+        }
+        self.generation += 1;
+        stats
     }
 
-    fn gen_species(&mut self) {
-        for species in &mut self.species {
-            species.reset();
+    pub fn try_evolve(&mut self) -> Result<GenerationStats, NeatError> {
+        if self.clients.is_empty() {
+            return Err(NeatError::EmptyPopulation);
+        }
+        if let Some(client) = self
+            .clients
+            .values()
+            .find(|client| !client.score.is_finite())
+        {
+            return Err(NeatError::InvalidFitness {
+                client: client.id,
+                score: client.score,
+            });
+        }
+        Ok(self.evolve())
+    }
+
+    fn assert_valid_genomes(&self) {
+        for client in self.clients.values() {
+            if let Err(error) = client.genome.validate(self.config.recurrent) {
+                panic!("client {}: {}", client.id, error);
+            }
         }
+    }
+
+    fn record_best(&mut self) {
         for client in self.clients.values() {
-            if self
-                .species
-                .iter()
-                .any(|species| species.clients.contains(&client.id))
+            if client.score.is_finite()
+                && self
+                    .best
+                    .as_ref()
+                    .is_none_or(|best| client.score > best.score)
             {
-                continue;
+                self.best = Some(client.clone());
             }
-            let mut found = false;
-            for species in &mut self.species {
-                if species.insert(client, &self.clients, &self.config) {
-                    found = true;
-                    break;
-                }
+        }
+    }
+
+    fn record_hall_of_fame(&mut self) {
+        for client in self.clients.values() {
+            if self.hall_of_fame.qualifies(client.score) {
+                self.hall_of_fame.insert(HallOfFameEntry {
+                    genome: client.genome.clone(),
+                    score: client.score,
+                    generation: self.generation,
+                    species: self
+                        .species
+                        .iter()
+                        .find(|species| species.clients.contains(&client.id))
+                        .map(|species| species.id),
+                });
             }
-            if !found {
-                self.species.push(Species::new(client.id));
+        }
+    }
+
+    fn gen_species(&mut self) {
+        for species in &mut self.species {
+            species.reset(&mut self.random);
+        }
+        let unassigned: Vec<&Client> = self
+            .clients
+            .values()
+            .filter(|client| {
+                !self
+                    .species
+                    .iter()
+                    .any(|species| species.clients.contains(&client.id))
+            })
+            .collect();
+        #[cfg(feature = "rayon")]
+        let unassigned = unassigned.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let unassigned = unassigned.iter();
+        let clients = &self.clients;
+        let config = &self.config;
+        let species = &self.species;
+        let assignments: Vec<(Id, Option<usize>)> = unassigned
+            .map(|client| {
+                (
+                    client.id,
+                    species
+                        .iter()
+                        .position(|species| species.matches(client, clients, config)),
+                )
+            })
+            .collect();
+
+        let existing_species = self.species.len();
+        for (id, found) in assignments {
+            let client = &clients[&id];
+            match found {
+                Some(index) => self.species[index].insert_force(client),
+                None => {
+                    if !self.species[existing_species..]
+                        .iter_mut()
+                        .any(|species| species.insert(client, clients, config))
+                    {
+                        self.species
+                            .push(Species::new(self.species_id_gen.gen(), id));
+                    }
+                }
             }
         }
+        let min_score = self
+            .clients
+            .values()
+            .map(|client| client.score)
+            .filter(|score| score.is_finite())
+            .fold(f32::INFINITY, f32::min);
         for species in &mut self.species {
-            species.evaluate_score(&self.clients);
+            species.evaluate_score(&mut self.clients, min_score);
+            species.update_stagnation(&self.clients);
         }
         self.config.cp += self.config.cp_change_rate
             * (self.species.len() as f32 - self.config.target_species_count as f32);
         self.config.cp = self.config.cp.max(0.1);
     }
 
+    fn remove_stagnant_species(&mut self) {
+        let mut ranking: Vec<usize> = (0..self.species.len()).collect();
+        ranking.sort_by(|&a, &b| {
+            self.species[b]
+                .best_score
+                .total_cmp(&self.species[a].best_score)
+        });
+        ranking.truncate(self.config.protected_species);
+        for i in (0..self.species.len()).rev() {
+            if self.species[i].stagnation > self.config.stagnation_limit && !ranking.contains(&i) {
+                self.species.remove(i);
+            }
+        }
+    }
+
+    fn elites(&self) -> BTreeSet<Id> {
+        let mut ids: Vec<Id> = self.clients.keys().copied().collect();
+        ids.sort_by(|a, b| {
+            self.clients[b]
+                .ranking_score()
+                .total_cmp(&self.clients[a].ranking_score())
+        });
+        let mut elites: BTreeSet<Id> = ids.into_iter().take(self.config.elitism).collect();
+        for species in &self.species {
+            if species.clients.len() >= self.config.elitism_min_species_size {
+                if let Some(champion) = species.clients.iter().max_by(|a, b| {
+                    self.clients[a]
+                        .ranking_score()
+                        .total_cmp(&self.clients[b].ranking_score())
+                }) {
+                    elites.insert(*champion);
+                }
+            }
+        }
+        elites
+    }
+
     fn kill(&mut self) {
         for species in &mut self.species {
             species.kill(&self.clients, 1.0 - self.config.survivors_percentage);
@@ -105,51 +254,113 @@ impl Neat {
         }
     }
 
-    fn reproduce(&mut self) {
-        let ids: Vec<Id> = self.clients.keys().copied().collect();
-        for id in ids {
-            if self
-                .species
-                .iter()
-                .any(|species| species.clients.contains(&id))
-            {
-                continue;
-            }
-            let mut random = rand::thread_rng();
-            if let Ok(species) = self
-                .species
-                .choose_weighted_mut(&mut random, |species| species.score)
-            {
-                let genome = species.breed(&self.clients, &self.config);
-                let client = self.clients.get_mut(&id).unwrap();
-                client.genome = genome;
-                species.insert_force(client);
+    fn reproduce(&mut self, elites: &BTreeSet<Id>) {
+        if self.species.is_empty() {
+            return;
+        }
+        let free: Vec<Id> = self
+            .clients
+            .keys()
+            .copied()
+            .filter(|id| {
+                !elites.contains(id)
+                    && !self
+                        .species
+                        .iter()
+                        .any(|species| species.clients.contains(id))
+            })
+            .collect();
+
+        let counts = self.offspring_counts(free.len());
+        let mut offspring = Vec::with_capacity(free.len());
+        for (index, &count) in counts.iter().enumerate() {
+            for _ in 0..count {
+                let genome =
+                    self.species[index].breed(&self.clients, &self.config, &mut self.random);
+                offspring.push((index, genome));
             }
         }
+
+        for (id, (index, genome)) in free.into_iter().zip(offspring) {
+            let client = self.clients.get_mut(&id).unwrap();
+            client.genome = genome;
+            self.species[index].insert_force(client);
+        }
     }
 
-    fn mutate(&mut self) {
-        let mut ids: Vec<Id> = self.clients.keys().copied().collect();
+    fn offspring_counts(&self, offspring: usize) -> Vec<usize> {
+        let total: f32 = self.species.iter().map(|species| species.score).sum();
+        let shares: Vec<f32> = self
+            .species
+            .iter()
+            .map(|species| {
+                if total > 0.0 {
+                    offspring as f32 * species.score / total
+                } else {
+                    offspring as f32 / self.species.len() as f32
+                }
+            })
+            .collect();
+
+        let mut counts: Vec<usize> = shares.iter().map(|share| share.floor() as usize).collect();
+        let remaining = offspring.saturating_sub(counts.iter().sum());
+        let mut order: Vec<usize> = (0..shares.len()).collect();
+        order.sort_by(|&a, &b| {
+            (shares[b] - counts[b] as f32).total_cmp(&(shares[a] - counts[a] as f32))
+        });
+        for &index in order.iter().cycle().take(remaining) {
+            counts[index] += 1;
+        }
+        counts
+    }
+
+    fn mutate(&mut self, elites: &BTreeSet<Id>) {
+        let mut ids: Vec<Id> = self
+            .clients
+            .keys()
+            .filter(|id| !elites.contains(id))
+            .copied()
+            .collect();
         ids.sort_by(|a, b| {
-            self.clients
-                .get(a)
-                .unwrap()
-                .score
-                .partial_cmp(&self.clients.get(b).unwrap().score)
-                .unwrap()
+            self.clients[a]
+                .ranking_score()
+                .total_cmp(&self.clients[b].ranking_score())
         });
-        let skip = (self.clients.len() as f32 * (1.0 - self.config.clients_mutation_rate)) as usize;
-        for id in ids.into_iter().skip(skip) {
-            self.clients
-                .get_mut(&id)
-                .unwrap()
-                .genome
-                .mutate(&self.config, &mut self.connection_genes);
+        let skip = (ids.len() as f32 * (1.0 - self.config.clients_mutation_rate)) as usize;
+        let mut randoms: BTreeMap<Id, NeatRng> = ids
+            .into_iter()
+            .skip(skip)
+            .map(|id| (id, NeatRng::seed_from_u64(self.random.gen())))
+            .collect();
+        for (id, random) in &mut randoms {
+            self.clients.get_mut(id).unwrap().genome.mutate_structure(
+                &self.config,
+                &mut self.connection_genes,
+                &mut self.gene_gen,
+                random,
+            );
         }
+
+        let config = &self.config;
+        let genomes: Vec<(&mut Genome, NeatRng)> = self
+            .clients
+            .iter_mut()
+            .filter_map(|(id, client)| {
+                randoms
+                    .remove(id)
+                    .map(move |random| (&mut client.genome, random))
+            })
+            .collect();
+        #[cfg(feature = "rayon")]
+        let genomes = genomes.into_par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let genomes = genomes.into_iter();
+        genomes.for_each(|(genome, mut random)| genome.mutate_parameters(config, &mut random));
     }
 
     pub(crate) fn get_connection_gene(
         connection_genes: &mut HashSet<ConnectionGene>,
+        gene_gen: &mut GeneGenerator,
         node_from: NodeGene,
         node_to: NodeGene,
         weight: f32,
@@ -161,9 +372,381 @@ impl Neat {
         {
             *connection
         } else {
-            let connection = ConnectionGene::new(Gene::new(), node_from, node_to, weight, enabled);
+            let connection =
+                ConnectionGene::new(gene_gen.gen(), node_from, node_to, weight, enabled);
             connection_genes.insert(connection);
             connection
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xor(genome: &Genome) -> f32 {
+        let cases = [
+            ([0.0, 0.0], 0.0),
+            ([0.0, 1.0], 1.0),
+            ([1.0, 0.0], 1.0),
+            ([1.0, 1.0], 0.0),
+        ];
+        cases
+            .iter()
+            .map(|(input, expected)| {
+                let output = genome.calculate(input.to_vec())[0];
+                1.0 - (output - expected).powi(2)
+            })
+            .sum()
+    }
+
+    fn seeded(seed: u64) -> Neat {
+        Neat::new(NeatConfig::builder(2, 1).seed(seed).build().unwrap())
+    }
+
+    #[test]
+    fn same_seed_evolves_identical_populations() {
+        let mut neat1 = seeded(7);
+        let mut neat2 = seeded(7);
+        for _ in 0..40 {
+            neat1.evaluate(xor);
+            neat2.evaluate(xor);
+        }
+        assert!(neat1
+            .clients
+            .values()
+            .any(|client| !client.genome.hidden_nodes.is_empty()));
+        assert_eq!(
+            format!("{:?}", neat1.clients),
+            format!("{:?}", neat2.clients)
+        );
+        assert_eq!(format!("{:?}", neat1.random), format!("{:?}", neat2.random));
+    }
+
+    #[test]
+    fn non_finite_scores_never_become_best() {
+        let mut neat = seeded(1);
+        let mut first = true;
+        let stop = neat.run(
+            |_| {
+                if std::mem::take(&mut first) {
+                    f32::NAN
+                } else {
+                    10.0
+                }
+            },
+            &[
+                StopCondition::FitnessThreshold(9.0),
+                StopCondition::MaxGenerations(20),
+            ],
+        );
+        assert_eq!(stop, StopReason::FitnessThreshold(9.0));
+        assert_eq!(neat.best.unwrap().score, 10.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one stop condition is required")]
+    fn run_requires_a_stop_condition() {
+        seeded(1).run(|_| 1.0, &[]);
+    }
+
+    fn innovation_numbers(neat: &Neat) -> Vec<Vec<usize>> {
+        neat.clients
+            .values()
+            .map(|client| {
+                client
+                    .genome
+                    .connections
+                    .iter()
+                    .map(|connection| connection.innovation_number)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn populations_in_one_process_keep_their_own_innovations() {
+        let mut alone = seeded(3);
+        for _ in 0..20 {
+            alone.evaluate(xor);
+        }
+
+        let mut neat1 = seeded(3);
+        let mut neat2 = seeded(4);
+        for _ in 0..20 {
+            neat1.evaluate(xor);
+            neat2.evaluate(xor);
+        }
+        assert!(neat2.gene_gen.next_innovation_number() > 3);
+        assert_eq!(innovation_numbers(&neat1), innovation_numbers(&alone));
+        assert_eq!(
+            neat1.gene_gen.next_innovation_number(),
+            alone.gene_gen.next_innovation_number()
+        );
+    }
+
+    fn with_species(scores: &[&[f32]]) -> Neat {
+        let count = scores.iter().map(|species| species.len()).sum();
+        let mut neat = Neat::new(
+            NeatConfig::builder(2, 1)
+                .seed(0)
+                .max_clients(count)
+                .target_species_count(1)
+                .build()
+                .unwrap(),
+        );
+        let ids: Vec<Id> = neat.clients.keys().copied().collect();
+        let mut ids = ids.into_iter();
+        for species_scores in scores {
+            let mut members = Vec::new();
+            for &score in species_scores.iter() {
+                let id = ids.next().unwrap();
+                neat.clients.get_mut(&id).unwrap().score = score;
+                members.push(id);
+            }
+            let mut species = Species::new(neat.species_id_gen.gen(), members[0]);
+            species.clients = members;
+            neat.species.push(species);
+        }
+        let min_score = neat
+            .clients
+            .values()
+            .map(|client| client.score)
+            .filter(|score| score.is_finite())
+            .fold(f32::INFINITY, f32::min);
+        for species in &mut neat.species {
+            species.evaluate_score(&mut neat.clients, min_score);
+        }
+        neat
+    }
+
+    #[test]
+    fn offspring_counts_fill_every_free_slot() {
+        let neat = with_species(&[&[0.0, 1.3], &[2.7], &[0.4, 0.9, 5.1], &[3.3]]);
+        for offspring in 0..60 {
+            assert_eq!(
+                neat.offspring_counts(offspring).iter().sum::<usize>(),
+                offspring
+            );
+        }
+    }
+
+    #[test]
+    fn offspring_counts_follow_mean_adjusted_fitness() {
+        let neat = with_species(&[&[0.0, 2.0], &[3.0, 3.0]]);
+        assert_eq!(neat.offspring_counts(40), vec![10, 30]);
+        assert_eq!(neat.offspring_counts(4), vec![1, 3]);
+    }
+
+    #[test]
+    fn reproduce_places_every_client_in_one_species() {
+        let mut neat = seeded(2);
+        for _ in 0..10 {
+            neat.evaluate(xor);
+            for id in neat.clients.keys() {
+                let homes = neat
+                    .species
+                    .iter()
+                    .filter(|species| species.clients.contains(id))
+                    .count();
+                assert_eq!(homes, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn stagnant_species_are_culled_unless_protected() {
+        let mut neat = with_species(&[&[5.0], &[1.0], &[2.0], &[0.0]]);
+        neat.config.protected_species = 1;
+        let limit = neat.config.stagnation_limit;
+        let ids: Vec<Id> = neat.species.iter().map(|species| species.id).collect();
+        for (species, (best_score, stagnation)) in neat.species.iter_mut().zip(vec![
+            (5.0, limit + 1),
+            (1.0, limit + 1),
+            (2.0, limit),
+            (0.0, 0),
+        ]) {
+            species.best_score = best_score;
+            species.stagnation = stagnation;
+        }
+
+        neat.remove_stagnant_species();
+        let remaining: Vec<Id> = neat.species.iter().map(|species| species.id).collect();
+        assert_eq!(remaining, vec![ids[0], ids[2], ids[3]]);
+    }
+
+    #[test]
+    fn plateau_never_removes_every_species() {
+        let mut neat = Neat::new(
+            NeatConfig::builder(2, 1)
+                .seed(4)
+                .protected_species(1)
+                .stagnation_limit(3)
+                .build()
+                .unwrap(),
+        );
+        for _ in 0..60 {
+            neat.evaluate(|_| 1.0);
+            assert!(!neat.species.is_empty());
+        }
+    }
+
+    fn genome_snapshot(neat: &Neat, ids: &BTreeSet<Id>) -> Vec<String> {
+        ids.iter()
+            .map(|id| format!("{:?}", neat.clients[id].genome))
+            .collect()
+    }
+
+    #[test]
+    fn top_clients_survive_evolve_unchanged() {
+        let mut neat = Neat::new(
+            NeatConfig::builder(2, 1)
+                .seed(6)
+                .elitism(5)
+                .probability_mutate_link(1.0)
+                .probability_mutate_node(0.5)
+                .clients_mutation_rate(1.0)
+                .build()
+                .unwrap(),
+        );
+        for _ in 0..10 {
+            neat.evaluate(xor);
+        }
+        for _ in 0..10 {
+            for client in neat.clients.values_mut() {
+                client.score = xor(&client.genome);
+            }
+            let mut ranked: Vec<&Client> = neat.clients.values().collect();
+            ranked.sort_by(|a, b| b.ranking_score().total_cmp(&a.ranking_score()));
+            let top: BTreeSet<Id> = ranked.iter().take(5).map(|client| client.id).collect();
+            let before = genome_snapshot(&neat, &top);
+            neat.evolve();
+            assert_eq!(genome_snapshot(&neat, &top), before);
+        }
+    }
+
+    #[test]
+    fn species_champions_survive_breeding_and_mutation_unchanged() {
+        let mut neat = Neat::new(
+            NeatConfig::builder(2, 1)
+                .seed(8)
+                .elitism_min_species_size(3)
+                .probability_mutate_link(1.0)
+                .clients_mutation_rate(1.0)
+                .build()
+                .unwrap(),
+        );
+        for _ in 0..10 {
+            neat.evaluate(xor);
+        }
+        for client in neat.clients.values_mut() {
+            client.score = xor(&client.genome);
+        }
+
+        neat.record_best();
+        neat.gen_species();
+        neat.remove_stagnant_species();
+        let elites = neat.elites();
+        let champions: BTreeSet<Id> = neat
+            .species
+            .iter()
+            .filter(|species| species.clients.len() >= 3)
+            .map(|species| {
+                *species
+                    .clients
+                    .iter()
+                    .max_by(|a, b| {
+                        neat.clients[a]
+                            .ranking_score()
+                            .total_cmp(&neat.clients[b].ranking_score())
+                    })
+                    .unwrap()
+            })
+            .collect();
+        assert!(!champions.is_empty());
+        assert_eq!(elites, champions);
+
+        let before = genome_snapshot(&neat, &elites);
+        neat.kill();
+        neat.remove_extinct_species();
+        neat.reproduce(&elites);
+        neat.mutate(&elites);
+        assert_eq!(genome_snapshot(&neat, &elites), before);
+    }
+
+    #[test]
+    fn zero_scores_split_offspring_evenly() {
+        let neat = with_species(&[&[0.0, 0.0], &[0.0], &[0.0, 0.0, 0.0]]);
+        assert_eq!(neat.offspring_counts(30), vec![10, 10, 10]);
+        let counts = neat.offspring_counts(31);
+        assert_eq!(counts.iter().sum::<usize>(), 31);
+        assert!(counts.iter().all(|&count| count == 10 || count == 11));
+    }
+
+    #[test]
+    fn negative_and_nan_scores_still_get_offspring() {
+        let neat = with_species(&[&[-5.0, -5.0], &[f32::NAN, 1.0], &[-1.0]]);
+        assert_eq!(neat.offspring_counts(14), vec![0, 6, 8]);
+
+        let neat = with_species(&[&[-3.0, -3.0], &[-3.0]]);
+        assert_eq!(neat.offspring_counts(10), vec![5, 5]);
+
+        let neat = with_species(&[&[f32::NAN], &[f32::NAN, f32::NAN]]);
+        assert_eq!(neat.offspring_counts(10), vec![5, 5]);
+    }
+
+    #[test]
+    fn try_new_rejects_invalid_configs() {
+        let config = NeatConfig {
+            max_clients: 0,
+            ..NeatConfig::default()
+        };
+        assert!(matches!(
+            Neat::try_new(config),
+            Err(NeatError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn try_evolve_rejects_non_finite_scores() {
+        for &score in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY].iter() {
+            let mut neat = seeded(1);
+            let id = *neat.clients.keys().nth(3).unwrap();
+            neat.clients.get_mut(&id).unwrap().score = score;
+            match neat.try_evolve() {
+                Err(NeatError::InvalidFitness {
+                    client,
+                    score: found,
+                }) => {
+                    assert_eq!(client, id);
+                    assert_eq!(found.to_bits(), score.to_bits());
+                }
+                other => panic!("unexpected result {:?}", other),
+            }
+            assert_eq!(neat.generation, 0);
+        }
+    }
+
+    #[test]
+    fn try_evolve_rejects_empty_population() {
+        let mut neat = seeded(1);
+        neat.clients.clear();
+        assert!(matches!(neat.try_evolve(), Err(NeatError::EmptyPopulation)));
+        let mut neat = seeded(1);
+        assert!(neat.try_evolve().is_ok());
+    }
+
+    #[test]
+    fn different_seeds_diverge() {
+        let mut neat1 = seeded(7);
+        let mut neat2 = seeded(8);
+        for _ in 0..10 {
+            neat1.evaluate(xor);
+            neat2.evaluate(xor);
+        }
+        assert_ne!(
+            format!("{:?}", neat1.clients),
+            format!("{:?}", neat2.clients)
+        );
+    }
+}
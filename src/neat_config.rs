@@ -0,0 +1,293 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NeatConfig {
+    pub input_size: usize,
+    pub output_size: usize,
+    pub max_clients: usize,
+    pub disjoint: f32,
+    pub excess: f32,
+    pub weight_diff: f32,
+    pub bias_diff: f32,
+    pub cp: f32,
+    pub cp_change_rate: f32,
+    pub target_species_count: usize,
+    pub probability_mutate_link: f32,
+    pub probability_mutate_node: f32,
+    pub probability_mutate_weight_shift: f32,
+    pub probability_mutate_weight_random: f32,
+    pub probability_mutate_link_toggle: f32,
+    pub weight_shift_strength: f32,
+    pub weight_random_strength: f32,
+    pub probability_mutate_bias_shift: f32,
+    pub probability_mutate_bias_random: f32,
+    pub bias_shift_strength: f32,
+    pub bias_random_strength: f32,
+    pub clients_mutation_rate: f32,
+    pub survivors_percentage: f32,
+    pub stagnation_limit: usize,
+    pub protected_species: usize,
+    pub elitism: usize,
+    pub elitism_min_species_size: usize,
+    pub hall_of_fame_size: usize,
+    pub seed: Option<u64>,
+    pub recurrent: bool,
+    pub hidden_activation: Activation,
+    pub output_activation: Activation,
+    pub activation_options: Vec<Activation>,
+    pub probability_mutate_activation: f32,
+    pub probability_inherit_disabled: f32,
+}
+
+/// Defaults follow Stanley & Miikkulainen (2002): c1 = c2 = 1.0, c3 = 0.4, δt = 3.0,
+/// a population of 150, steepened sigmoid nodes, 80% weight mutation and a 15 generation
+/// stagnation limit with species champions kept when a species has more than five members.
+/// Structural mutation rates are raised (the paper's large-population link rate and a higher
+/// node rate) because each mutation here touches a single gene of a fraction of the clients.
+impl Default for NeatConfig {
+    fn default() -> Self {
+        Self {
+            input_size: 1,
+            output_size: 1,
+            max_clients: 150,
+            disjoint: 1.0,
+            excess: 1.0,
+            weight_diff: 0.4,
+            bias_diff: 0.4,
+            cp: 3.0,
+            cp_change_rate: 0.1,
+            target_species_count: 10,
+            probability_mutate_link: 0.3,
+            probability_mutate_node: 0.1,
+            probability_mutate_weight_shift: 0.8,
+            probability_mutate_weight_random: 0.1,
+            probability_mutate_link_toggle: 0.01,
+            weight_shift_strength: 0.5,
+            weight_random_strength: 1.0,
+            probability_mutate_bias_shift: 0.7,
+            probability_mutate_bias_random: 0.1,
+            bias_shift_strength: 0.5,
+            bias_random_strength: 1.0,
+            clients_mutation_rate: 0.8,
+            survivors_percentage: 0.2,
+            stagnation_limit: 15,
+            protected_species: 2,
+            elitism: 0,
+            elitism_min_species_size: 6,
+            hall_of_fame_size: 10,
+            seed: None,
+            recurrent: false,
+            hidden_activation: Activation::SteepenedSigmoid,
+            output_activation: Activation::SteepenedSigmoid,
+            activation_options: Vec::new(),
+            probability_mutate_activation: 0.0,
+            probability_inherit_disabled: 0.75,
+        }
+    }
+}
+
+impl NeatConfig {
+    pub fn builder(input_size: usize, output_size: usize) -> NeatConfigBuilder {
+        NeatConfigBuilder {
+            config: NeatConfig {
+                input_size,
+                output_size,
+                ..NeatConfig::default()
+            },
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), NeatError> {
+        fn invalid(message: String) -> Result<(), NeatError> {
+            Err(NeatError::InvalidConfig(message))
+        }
+
+        if self.input_size == 0 {
+            return invalid("input_size must be positive".to_owned());
+        }
+        if self.output_size == 0 {
+            return invalid("output_size must be positive".to_owned());
+        }
+        if self.max_clients == 0 {
+            return invalid("max_clients must be positive".to_owned());
+        }
+        if self.target_species_count == 0 || self.target_species_count > self.max_clients {
+            return invalid(format!(
+                "target_species_count must be within [1, max_clients = {}], got {}",
+                self.max_clients, self.target_species_count
+            ));
+        }
+        if self.protected_species == 0 {
+            return invalid("protected_species must be positive".to_owned());
+        }
+        if self.elitism >= self.max_clients {
+            return invalid(format!(
+                "elitism must be less than max_clients = {}, got {}",
+                self.max_clients, self.elitism
+            ));
+        }
+
+        let probabilities = [
+            ("probability_mutate_link", self.probability_mutate_link),
+            ("probability_mutate_node", self.probability_mutate_node),
+            (
+                "probability_mutate_weight_shift",
+                self.probability_mutate_weight_shift,
+            ),
+            (
+                "probability_mutate_weight_random",
+                self.probability_mutate_weight_random,
+            ),
+            (
+                "probability_mutate_link_toggle",
+                self.probability_mutate_link_toggle,
+            ),
+            (
+                "probability_mutate_bias_shift",
+                self.probability_mutate_bias_shift,
+            ),
+            (
+                "probability_mutate_bias_random",
+                self.probability_mutate_bias_random,
+            ),
+            (
+                "probability_mutate_activation",
+                self.probability_mutate_activation,
+            ),
+            ("clients_mutation_rate", self.clients_mutation_rate),
+            (
+                "probability_inherit_disabled",
+                self.probability_inherit_disabled,
+            ),
+        ];
+        for (name, value) in probabilities.iter() {
+            if !(0.0..=1.0).contains(value) {
+                return invalid(format!("{} must be within [0, 1], got {}", name, value));
+            }
+        }
+        if !(self.survivors_percentage > 0.0 && self.survivors_percentage <= 1.0) {
+            return invalid(format!(
+                "survivors_percentage must be within (0, 1], got {}",
+                self.survivors_percentage
+            ));
+        }
+
+        let non_negative = [
+            ("disjoint", self.disjoint),
+            ("excess", self.excess),
+            ("weight_diff", self.weight_diff),
+            ("bias_diff", self.bias_diff),
+            ("cp_change_rate", self.cp_change_rate),
+            ("weight_shift_strength", self.weight_shift_strength),
+            ("weight_random_strength", self.weight_random_strength),
+            ("bias_shift_strength", self.bias_shift_strength),
+            ("bias_random_strength", self.bias_random_strength),
+        ];
+        for (name, value) in non_negative.iter() {
+            if !(value.is_finite() && *value >= 0.0) {
+                return invalid(format!(
+                    "{} must be finite and non-negative, got {}",
+                    name, value
+                ));
+            }
+        }
+        if !(self.cp.is_finite() && self.cp > 0.0) {
+            return invalid(format!("cp must be finite and positive, got {}", self.cp));
+        }
+
+        if self.probability_mutate_activation > 0.0 && self.activation_options.is_empty() {
+            return invalid(
+                "activation_options must not be empty when probability_mutate_activation is positive"
+                    .to_owned(),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NeatConfigBuilder {
+    config: NeatConfig,
+}
+
+macro_rules! builder_setters {
+    ($($field:ident: $ty:ty,)*) => {
+        $(
+            pub fn $field(mut self, $field: $ty) -> Self {
+                self.config.$field = $field;
+                self
+            }
+        )*
+    };
+}
+
+impl NeatConfigBuilder {
+    builder_setters! {
+        max_clients: usize,
+        disjoint: f32,
+        excess: f32,
+        weight_diff: f32,
+        bias_diff: f32,
+        cp: f32,
+        cp_change_rate: f32,
+        target_species_count: usize,
+        probability_mutate_link: f32,
+        probability_mutate_node: f32,
+        probability_mutate_weight_shift: f32,
+        probability_mutate_weight_random: f32,
+        probability_mutate_link_toggle: f32,
+        weight_shift_strength: f32,
+        weight_random_strength: f32,
+        probability_mutate_bias_shift: f32,
+        probability_mutate_bias_random: f32,
+        bias_shift_strength: f32,
+        bias_random_strength: f32,
+        clients_mutation_rate: f32,
+        survivors_percentage: f32,
+        stagnation_limit: usize,
+        protected_species: usize,
+        elitism: usize,
+        elitism_min_species_size: usize,
+        hall_of_fame_size: usize,
+        recurrent: bool,
+        hidden_activation: Activation,
+        output_activation: Activation,
+        activation_options: Vec<Activation>,
+        probability_mutate_activation: f32,
+        probability_inherit_disabled: f32,
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<NeatConfig, NeatError> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elitism_must_leave_room_for_offspring() {
+        let builder = NeatConfig::builder(2, 1).max_clients(10);
+        assert!(builder.clone().elitism(9).build().is_ok());
+        assert!(matches!(
+            builder.elitism(10).build(),
+            Err(NeatError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn best_species_is_always_protected() {
+        assert!(matches!(
+            NeatConfig::builder(2, 1).protected_species(0).build(),
+            Err(NeatError::InvalidConfig(_))
+        ));
+    }
+}
@@ -0,0 +1,302 @@
+use super::*;
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct Network {
+    input_size: usize,
+    activations: Vec<ActivationFunction>,
+    biases: Vec<f32>,
+    offsets: Vec<usize>,
+    sources: Vec<usize>,
+    weights: Vec<f32>,
+    outputs: Vec<usize>,
+    values: Vec<f32>,
+}
+
+impl Network {
+    pub fn input_size(&self) -> usize {
+        self.input_size
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.outputs.len()
+    }
+
+    pub fn reset_state(&mut self) {
+        for value in &mut self.values {
+            *value = 0.0;
+        }
+    }
+
+    pub fn activate(&mut self, input: &[f32], output: &mut [f32]) {
+        assert_eq!(input.len(), self.input_size);
+        assert_eq!(output.len(), self.outputs.len());
+
+        self.values[..self.input_size].copy_from_slice(input);
+        for node in 0..self.offsets.len() - 1 {
+            let mut sum = 0.0;
+            for link in self.offsets[node]..self.offsets[node + 1] {
+                sum += self.weights[link] * self.values[self.sources[link]];
+            }
+            self.values[self.input_size + node] = self.activations[node](sum + self.biases[node]);
+        }
+        for (value, &index) in output.iter_mut().zip(&self.outputs) {
+            *value = self.values[index];
+        }
+    }
+
+    /// Activates each row of `inputs` from a fresh state, so recurrent networks give the same
+    /// output for a row regardless of its position in the batch.
+    pub fn activate_batch(&mut self, inputs: &[f32], outputs: &mut [f32]) {
+        assert!(
+            self.input_size > 0,
+            "batch activation requires at least one input"
+        );
+        assert_eq!(inputs.len() % self.input_size, 0);
+        let rows = inputs.len() / self.input_size;
+        assert_eq!(outputs.len(), rows * self.outputs.len());
+
+        for (input, output) in inputs
+            .chunks_exact(self.input_size)
+            .zip(outputs.chunks_exact_mut(self.outputs.len()))
+        {
+            self.reset_state();
+            self.activate(input, output);
+        }
+    }
+}
+
+impl Genome {
+    pub fn calculate_batch(&self, inputs: &[f32]) -> Vec<f32> {
+        assert!(
+            !self.input_nodes.is_empty(),
+            "batch calculation requires at least one input"
+        );
+        let mut network = self.compile();
+        let mut outputs =
+            vec![0.0; inputs.len() / self.input_nodes.len() * self.output_nodes.len()];
+        network.activate_batch(inputs, &mut outputs);
+        outputs
+    }
+
+    pub fn compile(&self) -> Network {
+        let mut incoming: HashMap<Gene, Vec<&ConnectionGene>> = HashMap::new();
+        for connection in self
+            .connections
+            .iter()
+            .filter(|connection| connection.enabled)
+        {
+            incoming
+                .entry(connection.node_to.gene)
+                .or_default()
+                .push(connection);
+        }
+
+        let input_size = self.input_nodes.len();
+        let mut indices = HashMap::with_capacity(
+            self.input_nodes.len() + self.hidden_nodes.len() + self.output_nodes.len(),
+        );
+        for (index, input_node) in self.input_nodes.iter().enumerate() {
+            indices.insert(input_node.gene, index);
+        }
+
+        let mut order = Vec::new();
+        let mut stack = Vec::new();
+        for output_node in &self.output_nodes {
+            if indices.contains_key(&output_node.gene) {
+                continue;
+            }
+            stack.push((output_node.gene, 0));
+            while let Some((gene, next)) = stack.pop() {
+                if next == 0 && indices.contains_key(&gene) {
+                    continue;
+                }
+                if next == 0 {
+                    indices.insert(gene, usize::MAX);
+                }
+                let links = incoming.get(&gene).map(Vec::as_slice).unwrap_or_default();
+                match links.get(next) {
+                    Some(link) => {
+                        stack.push((gene, next + 1));
+                        if !indices.contains_key(&link.node_from.gene) {
+                            stack.push((link.node_from.gene, 0));
+                        }
+                    }
+                    None => {
+                        indices.insert(gene, input_size + order.len());
+                        order.push(gene);
+                    }
+                }
+            }
+        }
+
+        let mut offsets = Vec::with_capacity(order.len() + 1);
+        let mut sources = Vec::new();
+        let mut weights = Vec::new();
+        offsets.push(0);
+        for gene in &order {
+            for link in incoming.get(gene).map(Vec::as_slice).unwrap_or_default() {
+                sources.push(indices[&link.node_from.gene]);
+                weights.push(link.weight);
+            }
+            offsets.push(sources.len());
+        }
+
+        Network {
+            input_size,
+            activations: order
+                .iter()
+                .map(|&gene| {
+                    self.node(gene)
+                        .map(|node| node.activation)
+                        .unwrap_or_default()
+                        .function()
+                })
+                .collect(),
+            biases: order
+                .iter()
+                .map(|&gene| self.node(gene).map_or(0.0, |node| node.bias))
+                .collect(),
+            offsets,
+            sources,
+            weights,
+            outputs: self
+                .output_nodes
+                .iter()
+                .map(|output_node| indices[&output_node.gene])
+                .collect(),
+            values: vec![0.0; input_size + order.len()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evolved_genomes() -> Vec<Genome> {
+        let config = NeatConfig::builder(3, 2)
+            .seed(5)
+            .probability_mutate_node(0.4)
+            .probability_mutate_link(0.6)
+            .probability_mutate_link_toggle(0.3)
+            .hidden_activation(Activation::Tanh)
+            .activation_options(vec![
+                Activation::Relu,
+                Activation::Gaussian,
+                Activation::Sine,
+                Activation::LeakyRelu,
+            ])
+            .probability_mutate_activation(0.5)
+            .build()
+            .unwrap();
+        let mut neat = Neat::new(config);
+        for _ in 0..30 {
+            neat.evaluate(|genome| genome.calculate(vec![0.2, -0.4, 0.9])[0]);
+        }
+        neat.clients
+            .values()
+            .map(|client| client.genome.clone())
+            .collect()
+    }
+
+    fn bits(values: &[f32]) -> Vec<u32> {
+        values.iter().map(|value| value.to_bits()).collect()
+    }
+
+    #[test]
+    fn self_loop_carries_state_until_reset() {
+        let config = NeatConfig::builder(1, 1)
+            .recurrent(true)
+            .output_activation(Activation::Identity)
+            .build()
+            .unwrap();
+        let mut genome = Genome::empty(&config);
+        let input = genome.input_nodes[0];
+        let output = genome.output_nodes[0];
+        genome.connections.push(ConnectionGene::new(
+            Gene {
+                innovation_number: 2,
+            },
+            input,
+            output,
+            1.0,
+            true,
+        ));
+        genome.connections.push(ConnectionGene::new(
+            Gene {
+                innovation_number: 3,
+            },
+            output,
+            output,
+            0.5,
+            true,
+        ));
+        genome.validate(true).unwrap();
+
+        let mut network = genome.compile();
+        let mut value = [0.0];
+        network.activate(&[1.0], &mut value);
+        assert_eq!(value, [1.0]);
+        network.activate(&[1.0], &mut value);
+        assert_eq!(value, [1.5]);
+        network.activate(&[1.0], &mut value);
+        assert_eq!(value, [1.75]);
+
+        network.reset_state();
+        network.activate(&[1.0], &mut value);
+        assert_eq!(value, [1.0]);
+    }
+
+    #[test]
+    fn recurrent_batch_rows_are_independent() {
+        let config = NeatConfig::builder(2, 1)
+            .seed(9)
+            .recurrent(true)
+            .probability_mutate_node(0.4)
+            .probability_mutate_link(0.8)
+            .build()
+            .unwrap();
+        let mut neat = Neat::new(config);
+        for _ in 0..30 {
+            neat.evaluate(|genome| genome.calculate(vec![0.5, -0.5])[0]);
+        }
+
+        let rows = [0.1, 0.9, -0.7, 0.3, 1.5, -1.2];
+        let reversed = [1.5, -1.2, -0.7, 0.3, 0.1, 0.9];
+        for client in neat.clients.values() {
+            let forward = client.genome.calculate_batch(&rows);
+            let mut backward = client.genome.calculate_batch(&reversed);
+            backward.reverse();
+            assert_eq!(bits(&forward), bits(&backward));
+        }
+    }
+
+    #[test]
+    fn compiled_network_matches_calculate() {
+        let genomes = evolved_genomes();
+        assert!(genomes.iter().any(|genome| !genome.hidden_nodes.is_empty()));
+        assert!(genomes.iter().any(|genome| {
+            genome
+                .connections
+                .iter()
+                .any(|connection| !connection.enabled)
+        }));
+        assert!(genomes
+            .iter()
+            .flat_map(|genome| &genome.hidden_nodes)
+            .any(|node| node.bias != 0.0 && node.activation != Activation::Tanh));
+
+        let inputs = [[0.0, 0.0, 0.0], [1.0, -1.0, 0.5], [-0.3, 2.5, -1.7]];
+        let mut output = [0.0; 2];
+        for genome in &genomes {
+            let mut network = genome.compile();
+            for input in &inputs {
+                network.activate(input, &mut output);
+                let expected = genome.calculate(input.to_vec());
+                assert_eq!(bits(&output), bits(&expected));
+            }
+        }
+    }
+}
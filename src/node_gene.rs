@@ -1,42 +1,47 @@
 use super::*;
-use std::collections::HashMap;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct NodeGene {
     pub gene: Gene,
     pub x: f32,
     pub y: f32,
+    pub activation: Activation,
+    pub bias: f32,
 }
 
 impl NodeGene {
-    pub fn new(innovation_number: usize, x: f32, y: f32) -> Self {
+    pub fn new(innovation_number: usize, x: f32, y: f32, activation: Activation) -> Self {
         Self {
             gene: Gene { innovation_number },
             x,
             y,
+            activation,
+            bias: 0.0,
         }
     }
-    pub fn calculate(
-        &self,
-        connections: &Vec<ConnectionGene>,
-        output: &mut HashMap<Gene, f32>,
-    ) -> f32 {
-        let value = Self::activate(
-            connections
+    pub fn calculate(&self, genome: &Genome, output: &mut BTreeMap<Gene, f32>) -> f32 {
+        output.entry(self.gene).or_insert(0.0);
+        let value = self.activation.apply(
+            genome
+                .connections
                 .iter()
                 .filter(|connection| connection.enabled && connection.node_to == *self)
                 .map(|connection| match output.get(&connection.node_from.gene) {
                     Some(value) => connection.weight * value,
-                    None => connection.weight * connection.node_from.calculate(connections, output),
+                    None => {
+                        connection.weight
+                            * genome
+                                .node(connection.node_from.gene)
+                                .unwrap_or(&connection.node_from)
+                                .calculate(genome, output)
+                    }
                 })
-                .sum(),
+                .sum::<f32>()
+                + self.bias,
         );
         output.insert(self.gene, value);
         value
     }
-    fn activate(value: f32) -> f32 {
-        1.0 / (1.0 + (-value).exp())
-    }
 }
 
 impl PartialEq for NodeGene {
@@ -47,6 +52,24 @@ impl PartialEq for NodeGene {
 
 impl Eq for NodeGene {}
 
+impl PartialOrd for NodeGene {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NodeGene {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.gene.cmp(&other.gene)
+    }
+}
+
+impl std::borrow::Borrow<Gene> for NodeGene {
+    fn borrow(&self) -> &Gene {
+        &self.gene
+    }
+}
+
 impl std::hash::Hash for NodeGene {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.gene.hash(state);
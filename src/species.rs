@@ -1,33 +1,48 @@
 use super::*;
-use rand::Rng;
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Species {
+    pub id: Id,
     pub clients: Vec<Id>,
     pub representative: Id,
     pub score: f32,
+    pub age: usize,
+    pub best_score: f32,
+    pub stagnation: usize,
 }
 
 impl Species {
-    pub fn new(representative: Id) -> Self {
+    pub fn new(id: Id, representative: Id) -> Self {
         Self {
+            id,
             clients: vec![representative],
             representative,
             score: 0.0,
+            age: 0,
+            best_score: f32::NEG_INFINITY,
+            stagnation: 0,
         }
     }
 
-    pub fn insert(
-        &mut self,
+    pub fn matches(
+        &self,
         client: &Client,
-        all_clients: &HashMap<Id, Client>,
+        all_clients: &BTreeMap<Id, Client>,
         neat_config: &NeatConfig,
     ) -> bool {
-        if client.genome.distance(
+        client.genome.distance(
             &all_clients.get(&self.representative).unwrap().genome,
             neat_config,
         ) < neat_config.cp
-        {
+    }
+
+    pub fn insert(
+        &mut self,
+        client: &Client,
+        all_clients: &BTreeMap<Id, Client>,
+        neat_config: &NeatConfig,
+    ) -> bool {
+        if self.matches(client, all_clients, neat_config) {
             self.clients.push(client.id);
             true
         } else {
@@ -39,51 +54,107 @@ impl Species {
         self.clients.push(client.id);
     }
 
-    pub fn reset(&mut self) {
-        let mut random = rand::thread_rng();
+    pub fn reset(&mut self, random: &mut impl Rng) {
+        if self.clients.is_empty() {
+            return;
+        }
         let representative = self.clients.remove(random.gen_range(0, self.clients.len()));
         self.clients.clear();
         self.clients.push(representative);
         self.score = 0.0;
     }
 
-    pub fn evaluate_score(&mut self, all_clients: &HashMap<Id, Client>) {
-        self.score = self
+    pub fn evaluate_score(&mut self, all_clients: &mut BTreeMap<Id, Client>, min_score: f32) {
+        let size = self.clients.len() as f32;
+        self.score = 0.0;
+        for id in &self.clients {
+            let client = all_clients.get_mut(id).unwrap();
+            client.adjusted_score = if client.score.is_finite() {
+                (client.score - min_score) / size
+            } else {
+                0.0
+            };
+            self.score += client.adjusted_score;
+        }
+    }
+
+    pub fn update_stagnation(&mut self, all_clients: &BTreeMap<Id, Client>) {
+        let best_score = self
             .clients
             .iter()
             .map(|client| all_clients.get(client).unwrap().score)
-            .sum();
+            .filter(|score| score.is_finite())
+            .fold(f32::NEG_INFINITY, f32::max);
+        if best_score > self.best_score {
+            self.best_score = best_score;
+            self.stagnation = 0;
+        } else {
+            self.stagnation += 1;
+        }
+        self.age += 1;
     }
 
-    pub fn kill(&mut self, all_clients: &HashMap<Id, Client>, percentage: f32) {
+    pub fn kill(&mut self, all_clients: &BTreeMap<Id, Client>, percentage: f32) {
         self.clients.sort_by(|client1, client2| {
-            all_clients
-                .get(client1)
-                .unwrap()
-                .score
-                .partial_cmp(&all_clients.get(client2).unwrap().score)
-                .unwrap()
+            all_clients[client1]
+                .ranking_score()
+                .total_cmp(&all_clients[client2].ranking_score())
         });
         for _ in 0..((self.clients.len() as f32 * percentage).floor() as usize) {
             self.clients.remove(0);
         }
     }
 
-    pub fn breed(&self, all_clients: &HashMap<Id, Client>, neat_config: &NeatConfig) -> Genome {
+    pub fn breed(
+        &self,
+        all_clients: &BTreeMap<Id, Client>,
+        neat_config: &NeatConfig,
+        random: &mut impl Rng,
+    ) -> Genome {
         let client1 = &self
             .clients
-            .get(rand::thread_rng().gen_range(0, self.clients.len()))
+            .get(random.gen_range(0, self.clients.len()))
             .unwrap();
         let client1 = all_clients.get(client1).unwrap();
         let client2 = &self
             .clients
-            .get(rand::thread_rng().gen_range(0, self.clients.len()))
+            .get(random.gen_range(0, self.clients.len()))
             .unwrap();
         let client2 = all_clients.get(client2).unwrap();
-        if client1.score > client2.score {
-            Genome::cross_over(&client1.genome, &client2.genome, neat_config)
-        } else {
-            Genome::cross_over(&client2.genome, &client1.genome, neat_config)
-        }
+        Genome::cross_over(
+            &client1.genome,
+            client1.ranking_score(),
+            &client2.genome,
+            client2.ranking_score(),
+            neat_config,
+            random,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stagnation_tracks_best_score_and_age() {
+        let mut ids = IdGenerator::new();
+        let client = Client::new(ids.gen(), Genome::empty(&NeatConfig::default()));
+        let id = client.id;
+        let mut clients = BTreeMap::new();
+        clients.insert(id, client);
+        let mut species = Species::new(ids.gen(), id);
+
+        let mut update = |score: f32| {
+            clients.get_mut(&id).unwrap().score = score;
+            species.update_stagnation(&clients);
+            (species.age, species.best_score, species.stagnation)
+        };
+        assert_eq!(update(1.0), (1, 1.0, 0));
+        assert_eq!(update(1.0), (2, 1.0, 1));
+        assert_eq!(update(0.5), (3, 1.0, 2));
+        assert_eq!(update(f32::NAN), (4, 1.0, 3));
+        assert_eq!(update(2.0), (5, 2.0, 0));
+        assert_eq!(update(1.5), (6, 2.0, 1));
     }
 }
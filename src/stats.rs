@@ -0,0 +1,116 @@
+use super::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeciesStats {
+    pub id: Id,
+    pub size: usize,
+    pub score: f32,
+    pub best_score: f32,
+    pub age: usize,
+    pub stagnation: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best_score: f32,
+    pub mean_score: f32,
+    pub median_score: f32,
+    pub stdev_score: f32,
+    pub species: Vec<SpeciesStats>,
+    pub cp: f32,
+    pub mean_hidden_nodes: f32,
+    pub mean_enabled_connections: f32,
+    pub new_innovations: usize,
+}
+
+pub trait Reporter {
+    fn report(&mut self, stats: &GenerationStats);
+
+    fn should_stop(&mut self, _stats: &GenerationStats) -> bool {
+        false
+    }
+}
+
+impl<R: Reporter> Reporter for Arc<Mutex<R>> {
+    fn report(&mut self, stats: &GenerationStats) {
+        self.lock().unwrap().report(stats);
+    }
+
+    fn should_stop(&mut self, stats: &GenerationStats) -> bool {
+        self.lock().unwrap().should_stop(stats)
+    }
+}
+
+impl std::fmt::Debug for dyn Reporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Reporter")
+    }
+}
+
+impl GenerationStats {
+    pub fn new(neat: &Neat) -> Self {
+        let mut scores: Vec<f32> = neat
+            .clients
+            .values()
+            .map(|client| client.score)
+            .filter(|score| score.is_finite())
+            .collect();
+        scores.sort_by(f32::total_cmp);
+
+        let count = scores.len().max(1) as f32;
+        let mean_score = scores.iter().sum::<f32>() / count;
+        let variance = scores
+            .iter()
+            .map(|score| (score - mean_score) * (score - mean_score))
+            .sum::<f32>()
+            / count;
+        let median_score = match scores.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (scores[len / 2 - 1] + scores[len / 2]) / 2.0,
+            len => scores[len / 2],
+        };
+
+        let clients = neat.clients.len().max(1) as f32;
+        Self {
+            generation: neat.generation,
+            best_score: scores.last().copied().unwrap_or(0.0),
+            mean_score,
+            median_score,
+            stdev_score: variance.sqrt(),
+            species: neat
+                .species
+                .iter()
+                .map(|species| SpeciesStats {
+                    id: species.id,
+                    size: species.clients.len(),
+                    score: species.score,
+                    best_score: species.best_score,
+                    age: species.age,
+                    stagnation: species.stagnation,
+                })
+                .collect(),
+            cp: neat.config.cp,
+            mean_hidden_nodes: neat
+                .clients
+                .values()
+                .map(|client| client.genome.hidden_nodes.len())
+                .sum::<usize>() as f32
+                / clients,
+            mean_enabled_connections: neat
+                .clients
+                .values()
+                .map(|client| {
+                    client
+                        .genome
+                        .connections
+                        .iter()
+                        .filter(|connection| connection.enabled)
+                        .count()
+                })
+                .sum::<usize>() as f32
+                / clients,
+            new_innovations: 0,
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use super::*;
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatsFormat {
+    Csv,
+    #[cfg(feature = "json")]
+    JsonLines,
+}
+
+#[cfg_attr(feature = "json", derive(Serialize))]
+struct GenerationRecord {
+    generation: usize,
+    best_score: f32,
+    mean_score: f32,
+    median_score: f32,
+    stdev_score: f32,
+    species_count: usize,
+    cp: f32,
+    mean_hidden_nodes: f32,
+    mean_enabled_connections: f32,
+    new_innovations: usize,
+}
+
+#[cfg_attr(feature = "json", derive(Serialize))]
+struct SpeciesRecord {
+    generation: usize,
+    species: Id,
+    size: usize,
+    score: f32,
+    best_score: f32,
+    age: usize,
+    stagnation: usize,
+}
+
+const GENERATION_HEADER: &str = "generation,best_score,mean_score,median_score,stdev_score,species_count,cp,mean_hidden_nodes,mean_enabled_connections,new_innovations";
+const SPECIES_HEADER: &str = "generation,species,size,score,best_score,age,stagnation";
+
+#[derive(Debug)]
+pub struct StatsExporter<W: Write> {
+    format: StatsFormat,
+    generations: W,
+    species: W,
+    header_written: bool,
+    stop_on_error: bool,
+    error: Option<std::io::Error>,
+}
+
+impl StatsExporter<BufWriter<File>> {
+    pub fn create(
+        format: StatsFormat,
+        generations_path: impl AsRef<Path>,
+        species_path: impl AsRef<Path>,
+    ) -> std::io::Result<Self> {
+        Ok(Self::new(
+            format,
+            BufWriter::new(File::create(generations_path)?),
+            BufWriter::new(File::create(species_path)?),
+        ))
+    }
+}
+
+impl<W: Write> StatsExporter<W> {
+    pub fn new(format: StatsFormat, generations: W, species: W) -> Self {
+        Self {
+            format,
+            generations,
+            species,
+            header_written: false,
+            stop_on_error: false,
+            error: None,
+        }
+    }
+
+    pub fn stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+
+    pub fn error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn write(&mut self, stats: &GenerationStats) -> std::io::Result<()> {
+        let generation = GenerationRecord {
+            generation: stats.generation,
+            best_score: stats.best_score,
+            mean_score: stats.mean_score,
+            median_score: stats.median_score,
+            stdev_score: stats.stdev_score,
+            species_count: stats.species.len(),
+            cp: stats.cp,
+            mean_hidden_nodes: stats.mean_hidden_nodes,
+            mean_enabled_connections: stats.mean_enabled_connections,
+            new_innovations: stats.new_innovations,
+        };
+        let species = stats.species.iter().map(|species| SpeciesRecord {
+            generation: stats.generation,
+            species: species.id,
+            size: species.size,
+            score: species.score,
+            best_score: species.best_score,
+            age: species.age,
+            stagnation: species.stagnation,
+        });
+
+        match self.format {
+            StatsFormat::Csv => {
+                if !self.header_written {
+                    writeln!(self.generations, "{}", GENERATION_HEADER)?;
+                    writeln!(self.species, "{}", SPECIES_HEADER)?;
+                    self.header_written = true;
+                }
+                writeln!(
+                    self.generations,
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    generation.generation,
+                    generation.best_score,
+                    generation.mean_score,
+                    generation.median_score,
+                    generation.stdev_score,
+                    generation.species_count,
+                    generation.cp,
+                    generation.mean_hidden_nodes,
+                    generation.mean_enabled_connections,
+                    generation.new_innovations,
+                )?;
+                for species in species {
+                    writeln!(
+                        self.species,
+                        "{},{},{},{},{},{},{}",
+                        species.generation,
+                        species.species,
+                        species.size,
+                        species.score,
+                        species.best_score,
+                        species.age,
+                        species.stagnation,
+                    )?;
+                }
+            }
+            #[cfg(feature = "json")]
+            StatsFormat::JsonLines => {
+                serde_json::to_writer(&mut self.generations, &generation)?;
+                writeln!(self.generations)?;
+                for species in species {
+                    serde_json::to_writer(&mut self.species, &species)?;
+                    writeln!(self.species)?;
+                }
+            }
+        }
+
+        self.generations.flush()?;
+        self.species.flush()
+    }
+}
+
+impl<W: Write> Reporter for StatsExporter<W> {
+    fn report(&mut self, stats: &GenerationStats) {
+        if self.error.is_none() {
+            if let Err(error) = self.write(stats) {
+                self.error = Some(error);
+            }
+        }
+    }
+
+    fn should_stop(&mut self, _stats: &GenerationStats) -> bool {
+        self.stop_on_error && self.error.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn stats() -> GenerationStats {
+        GenerationStats::new(&Neat::new(
+            NeatConfig::builder(1, 1).seed(0).build().unwrap(),
+        ))
+    }
+
+    #[test]
+    fn write_errors_do_not_stop_by_default() {
+        let mut exporter = StatsExporter::new(StatsFormat::Csv, FailingWriter, FailingWriter);
+        exporter.report(&stats());
+        assert!(exporter.error().is_some());
+        assert!(!exporter.should_stop(&stats()));
+    }
+
+    #[test]
+    fn write_errors_stop_when_requested() {
+        let mut exporter =
+            StatsExporter::new(StatsFormat::Csv, FailingWriter, FailingWriter).stop_on_error(true);
+        exporter.report(&stats());
+        assert!(exporter.should_stop(&stats()));
+    }
+
+    fn neat() -> Neat {
+        Neat::new(NeatConfig::builder(1, 1).seed(0).build().unwrap())
+    }
+
+    #[test]
+    fn run_continues_and_keeps_the_error_by_default() {
+        let mut neat = neat();
+        let exporter = neat.add_reporter(StatsExporter::new(
+            StatsFormat::Csv,
+            FailingWriter,
+            FailingWriter,
+        ));
+        let stop = neat.run(|_| 1.0, &[StopCondition::MaxGenerations(3)]);
+        assert_eq!(stop, StopReason::MaxGenerations(3));
+        assert_eq!(
+            exporter.lock().unwrap().error().unwrap().to_string(),
+            "disk full"
+        );
+    }
+
+    #[test]
+    fn run_stops_on_error_when_requested() {
+        let mut neat = neat();
+        let exporter = neat.add_reporter(
+            StatsExporter::new(StatsFormat::Csv, FailingWriter, FailingWriter).stop_on_error(true),
+        );
+        let stop = neat.run(|_| 1.0, &[StopCondition::MaxGenerations(3)]);
+        assert_eq!(stop, StopReason::Reporter);
+        assert_eq!(neat.generation, 1);
+        assert!(exporter.lock().unwrap().error().is_some());
+    }
+
+    #[test]
+    fn csv_rows_follow_the_header() {
+        let mut exporter = StatsExporter::new(StatsFormat::Csv, Vec::new(), Vec::new());
+        exporter.report(&stats());
+        exporter.report(&stats());
+        assert!(exporter.error().is_none());
+        let generations = String::from_utf8(exporter.generations).unwrap();
+        let lines: Vec<&str> = generations.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], GENERATION_HEADER);
+    }
+}
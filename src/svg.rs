@@ -0,0 +1,207 @@
+use super::*;
+
+use std::fmt::Write;
+
+const SVG_WIDTH: f32 = 480.0;
+const SVG_HEIGHT: f32 = 320.0;
+const SVG_MARGIN: f32 = 24.0;
+const SVG_NODE_RADIUS: f32 = 8.0;
+
+impl Genome {
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with_values(None)
+    }
+
+    pub fn to_svg_with_values(&self, values: Option<&BTreeMap<Gene, f32>>) -> String {
+        let position = |node: &NodeGene| {
+            (
+                SVG_MARGIN + node.x * (SVG_WIDTH - 2.0 * SVG_MARGIN),
+                SVG_MARGIN + node.y * (SVG_HEIGHT - 2.0 * SVG_MARGIN),
+            )
+        };
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+            SVG_WIDTH, SVG_HEIGHT, SVG_WIDTH, SVG_HEIGHT,
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>"
+        )
+        .unwrap();
+
+        let max_weight = self
+            .connections
+            .iter()
+            .map(|connection| connection.weight.abs())
+            .fold(0.0, f32::max)
+            .max(f32::EPSILON);
+        for connection in &self.connections {
+            let (x1, y1) = position(
+                self.node(connection.node_from.gene)
+                    .unwrap_or(&connection.node_from),
+            );
+            let (x2, y2) = position(
+                self.node(connection.node_to.gene)
+                    .unwrap_or(&connection.node_to),
+            );
+            let strength = (connection.weight.abs() / max_weight).min(1.0);
+            let color = if connection.weight >= 0.0 {
+                "#1f4fd8"
+            } else {
+                "#d8281f"
+            };
+            let style = format!(
+                "stroke=\"{}\" stroke-width=\"{:.2}\" stroke-opacity=\"{:.2}\" fill=\"none\"{}",
+                color,
+                0.5 + 3.5 * strength,
+                0.25 + 0.75 * strength,
+                if connection.enabled {
+                    ""
+                } else {
+                    " stroke-dasharray=\"4 3\""
+                },
+            );
+            if connection.node_from == connection.node_to {
+                writeln!(
+                    svg,
+                    "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" {}/>",
+                    x1,
+                    y1 - SVG_NODE_RADIUS,
+                    SVG_NODE_RADIUS,
+                    style,
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    svg,
+                    "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>",
+                    x1, y1, x2, y2, style,
+                )
+                .unwrap();
+            }
+        }
+
+        let nodes = self
+            .input_nodes
+            .iter()
+            .map(|node| (node, "#a6c8ff"))
+            .chain(self.hidden_nodes.iter().map(|node| (node, "#f2f2f2")))
+            .chain(self.output_nodes.iter().map(|node| (node, "#ffb59e")));
+        for (node, fill) in nodes {
+            let (x, y) = position(node);
+            writeln!(
+                svg,
+                "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\" stroke=\"black\"><title>{} {}</title></circle>",
+                x,
+                y,
+                SVG_NODE_RADIUS,
+                fill,
+                node.gene.innovation_number,
+                escape_xml(&node.activation.to_string()),
+            )
+            .unwrap();
+            if let Some(value) = values.and_then(|values| values.get(&node.gene)) {
+                writeln!(
+                    svg,
+                    "  <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" font-family=\"monospace\" text-anchor=\"middle\">{:.3}</text>",
+                    x,
+                    y - SVG_NODE_RADIUS - 3.0,
+                    value,
+                )
+                .unwrap();
+            }
+        }
+
+        writeln!(svg, "</svg>").unwrap();
+        svg
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genome() -> Genome {
+        let mut genome = Genome::empty(&NeatConfig::builder(2, 1).build().unwrap());
+        let (input1, input2, output) = (
+            genome.input_nodes[0],
+            genome.input_nodes[1],
+            genome.output_nodes[0],
+        );
+        genome.connections.push(ConnectionGene::new(
+            Gene {
+                innovation_number: 3,
+            },
+            input1,
+            output,
+            1.0,
+            true,
+        ));
+        genome.connections.push(ConnectionGene::new(
+            Gene {
+                innovation_number: 4,
+            },
+            input2,
+            output,
+            -1.0,
+            false,
+        ));
+        genome
+    }
+
+    #[test]
+    fn values_are_drawn_above_nodes() {
+        let genome = genome();
+        let mut values = BTreeMap::new();
+        values.insert(
+            Gene {
+                innovation_number: 0,
+            },
+            0.5,
+        );
+        values.insert(
+            Gene {
+                innovation_number: 2,
+            },
+            -0.125,
+        );
+        let svg = genome.to_svg_with_values(Some(&values));
+        assert_eq!(svg.matches("<text ").count(), 2);
+        assert!(svg.contains(">0.500</text>"));
+        assert!(svg.contains(">-0.125</text>"));
+        assert!(!genome.to_svg().contains("<text "));
+
+        let computed = genome.calculate_debug(vec![1.0, 1.0]);
+        let svg = genome.to_svg_with_values(Some(&computed));
+        assert_eq!(svg.matches("<text ").count(), 3);
+    }
+
+    #[test]
+    fn only_disabled_connections_are_dashed() {
+        let svg = genome().to_svg();
+        let lines: Vec<&str> = svg.lines().filter(|line| line.contains("<line ")).collect();
+        assert_eq!(lines.len(), 2);
+        assert!(!lines[0].contains("stroke-dasharray"));
+        assert!(lines[1].contains("stroke-dasharray=\"4 3\""));
+    }
+
+    #[test]
+    fn activation_names_are_escaped() {
+        let mut genome = Genome::empty(&NeatConfig::default());
+        genome.output_nodes[0].activation = Activation::register("svg\"<b>&'", f32::abs);
+        let svg = genome.to_svg();
+        assert!(svg.contains("<title>1 svg&quot;&lt;b&gt;&amp;&apos;</title>"));
+    }
+}